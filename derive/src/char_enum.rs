@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitChar};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let variants = match &input.data {
+        Data::Enum(e) => &e.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut pairs: Vec<(LitChar, &syn::Ident)> = Vec::new();
+    for v in variants {
+        if !matches!(v.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                v,
+                "only unit variants are supported",
+            ));
+        }
+        let attr = v
+            .attrs
+            .iter()
+            .find(|a| a.path().is_ident("ch"))
+            .ok_or_else(|| syn::Error::new_spanned(v, "missing #[ch('.')] attribute"))?;
+        let ch: LitChar = attr.parse_args()?;
+        if let Some((_, other)) = pairs.iter().find(|(c, _)| c.value() == ch.value()) {
+            return Err(syn::Error::new_spanned(
+                &ch,
+                format!("{:?} is already used by `{}`", ch.value(), other),
+            ));
+        }
+        pairs.push((ch, &v.ident));
+    }
+
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let from_char = pairs
+        .iter()
+        .map(|(c, v)| quote! { #c => ::core::result::Result::Ok(#name::#v), });
+    let to_char = pairs.iter().map(|(c, v)| quote! { #name::#v => #c, });
+
+    Ok(quote! {
+        impl #impl_generics ::std::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = ::std::string::String;
+
+            fn try_from(c: char) -> ::std::result::Result<Self, Self::Error> {
+                match c {
+                    #(#from_char)*
+                    _ => ::core::result::Result::Err(::std::format!(
+                        "unexpected {:?} for {}",
+                        c,
+                        #name_str
+                    )),
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<&#name #ty_generics> for char #where_clause {
+            fn from(value: &#name #ty_generics) -> Self {
+                match value {
+                    #(#to_char)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::core::write!(f, "{}", char::from(self))
+            }
+        }
+    })
+}
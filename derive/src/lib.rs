@@ -0,0 +1,86 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Member, Type};
+
+mod char_enum;
+mod parse_line;
+
+#[proc_macro_derive(ParseLine, attributes(pattern))]
+pub fn derive_parse_line(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    parse_line::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(CharEnum, attributes(ch))]
+pub fn derive_char_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    char_enum::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn struct_fields(input: &DeriveInput) -> syn::Result<Vec<(Member, Type)>> {
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "can only be derived for structs",
+            ))
+        }
+    };
+    let members = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|f| (Member::Named(f.ident.clone().unwrap()), f.ty.clone()))
+            .collect(),
+        Fields::Unnamed(unnamed) => unnamed
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (Member::Unnamed(i.into()), f.ty.clone()))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+    Ok(members)
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(idx) => idx.index.to_string(),
+    }
+}
+
+fn member_var(member: &Member) -> Ident {
+    format_ident!("__field_{}", member_name(member), span = Span::call_site())
+}
+
+fn find_attr<'a>(input: &'a DeriveInput, name: &str) -> syn::Result<&'a syn::Attribute> {
+    input
+        .attrs
+        .iter()
+        .find(|a| a.path().is_ident(name))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.ident,
+                format!("missing #[{}(\"...\")] attribute", name),
+            )
+        })
+}
+
+fn attr_str(attr: &syn::Attribute) -> syn::Result<LitStr> {
+    attr.parse_args::<LitStr>()
+}
+
+fn quote_members(members: &[(Member, Type)]) -> proc_macro2::TokenStream {
+    let inits = members.iter().map(|(m, _)| {
+        let var = member_var(m);
+        quote! { #m: #var }
+    });
+    quote! { { #(#inits),* } }
+}
@@ -0,0 +1,151 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitStr};
+
+use crate::{attr_str, find_attr, member_name, member_var, quote_members, struct_fields};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let err = |msg: &str| syn::Error::new_spanned(pattern, msg);
+
+    let text = pattern.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(err("unclosed `{` in pattern")),
+                    }
+                }
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err(err("empty field name in pattern"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if let Some(Segment::Field(prev)) = segments.last() {
+                    return Err(err(&format!(
+                        "fields `{}` and `{}` need a separator between them",
+                        prev, name
+                    )));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err(err("unmatched `}` in pattern, use `}}` for a literal one")),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let attr = find_attr(&input, "pattern")?;
+    let pattern = attr_str(attr)?;
+    let segments = parse_pattern(&pattern)?;
+    let members = struct_fields(&input)?;
+
+    // every field has to be in the pattern exactly once
+    for (member, _) in members.iter() {
+        let name = member_name(member);
+        let count = segments
+            .iter()
+            .filter(|s| **s == Segment::Field(name.clone()))
+            .count();
+        if count != 1 {
+            return Err(syn::Error::new_spanned(
+                &pattern,
+                format!("field `{}` must appear in the pattern exactly once", name),
+            ));
+        }
+    }
+
+    let mut steps = Vec::new();
+    let mut iter = segments.iter().peekable();
+    let mut prev_field: Option<String> = None;
+    while let Some(segment) = iter.next() {
+        match segment {
+            Segment::Literal(lit) => {
+                let after = match &prev_field {
+                    None => String::from("at the start"),
+                    Some(f) => format!("after `{}`", f),
+                };
+                steps.push(quote! {
+                    let __rest = __rest.strip_prefix(#lit).ok_or_else(|| {
+                        format!("expected {:?} {} in {:?}", #lit, #after, __line)
+                    })?;
+                });
+            }
+            Segment::Field(name) => {
+                let (member, ty) = members
+                    .iter()
+                    .find(|(m, _)| member_name(m) == *name)
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(&pattern, format!("no field named `{}`", name))
+                    })?;
+                let var = member_var(member);
+                let raw = match iter.peek() {
+                    Some(Segment::Literal(next)) => quote! {
+                        let __idx = __rest.find(#next).ok_or_else(|| {
+                            format!("expected {:?} after `{}` in {:?}", #next, #name, __line)
+                        })?;
+                        let (__raw, __rest) = __rest.split_at(__idx);
+                    },
+                    _ => quote! {
+                        let (__raw, __rest) = (__rest, "");
+                    },
+                };
+                steps.push(quote! {
+                    #raw
+                    let #var = __raw.parse::<#ty>().map_err(|e| {
+                        format!("can't parse `{}` from {:?} in {:?}: {}", #name, __raw, __line, e)
+                    })?;
+                });
+                prev_field = Some(name.clone());
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let init = quote_members(&members);
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::std::string::String;
+
+            fn from_str(__line: &str) -> ::std::result::Result<Self, Self::Err> {
+                let __rest = __line;
+                #(#steps)*
+                if !__rest.is_empty() {
+                    return ::std::result::Result::Err(format!(
+                        "unexpected {:?} at the end of {:?}",
+                        __rest, __line
+                    ));
+                }
+                ::std::result::Result::Ok(#name #init)
+            }
+        }
+    })
+}
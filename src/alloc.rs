@@ -0,0 +1,47 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+static USED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static ABANDONED: AtomicBool = AtomicBool::new(false);
+
+// the binary installs it with #[global_allocator] to get memory numbers in reports
+pub struct PeakAlloc;
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ACTIVE.store(true, Ordering::Relaxed);
+            let used = USED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(used, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        USED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+// the most bytes allocated at once while `f` ran, above what was already in use,
+// None when PeakAlloc isn't the global allocator or a measured run was abandoned
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let before = USED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let res = f();
+    let peak = PEAK.load(Ordering::Relaxed);
+    let memory = (ACTIVE.load(Ordering::Relaxed) && !ABANDONED.load(Ordering::Relaxed))
+        .then(|| peak.saturating_sub(before) as u64);
+    (res, memory)
+}
+
+// for a measured run left going after a timeout, its allocations would end up in
+// whatever gets measured next, so nothing is measured from then on
+pub fn abandon() {
+    ABANDONED.store(true, Ordering::Relaxed);
+}
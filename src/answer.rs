@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Value {
+    Int(i64),
+    BigInt(u128),
+    Text(String),
+    // letters drawn with '#', has to be read by a human before submitting
+    Art(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answer {
+    pub value: Value,
+    // anything useful for debugging that isn't part of the answer itself
+    pub details: Option<String>,
+}
+
+impl Answer {
+    pub fn new(value: Value) -> Self {
+        Answer {
+            value,
+            details: None,
+        }
+    }
+
+    pub fn art(art: impl Into<String>) -> Self {
+        Answer::new(Value::Art(art.into()))
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+
+    // the exact string to send to the site, if there is one
+    pub fn submittable(&self) -> Option<String> {
+        match &self.value {
+            Value::Int(n) => Some(n.to_string()),
+            Value::BigInt(n) => Some(n.to_string()),
+            Value::Text(s) => Some(s.clone()),
+            Value::Art(_) => None,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::BigInt(n) => write!(f, "{}", n),
+            Value::Text(s) => write!(f, "{}", s),
+            Value::Art(art) => write!(f, "\n{}", art.trim_matches('\n')),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)?;
+        if let Some(details) = &self.details {
+            write!(f, " ({})", details)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::new(Value::Int(n)),
+                        Err(_) => Answer::new(Value::BigInt(n as u128)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i32, u32, i64, u64, usize, u128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::new(Value::Text(s))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::new(Value::Text(String::from(s)))
+    }
+}
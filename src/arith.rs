@@ -0,0 +1,100 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Sub},
+};
+
+// the arithmetic of the days that get close to the integer limits, built with
+// the checked feature an overflow stops the solver with an Overflow the runner
+// reports as an error, otherwise it's plain arithmetic
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub op: &'static str,
+    pub lhs: String,
+    pub rhs: String,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "overflow in {} {} {}", self.lhs, self.op, self.rhs)
+    }
+}
+
+pub trait Int:
+    Copy + Display + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })*
+    };
+}
+
+int!(i32, i64, u64, usize, u128);
+
+// unwinds without going through the panic hook, it's an error and not a bug
+#[cfg(feature = "checked")]
+fn overflow<T: Int>(op: &'static str, lhs: T, rhs: T) -> T {
+    std::panic::resume_unwind(Box::new(Overflow {
+        op,
+        lhs: lhs.to_string(),
+        rhs: rhs.to_string(),
+    }))
+}
+
+macro_rules! op {
+    ($name:ident, $checked:ident, $sym:literal, $op:tt) => {
+        #[cfg(feature = "checked")]
+        pub fn $name<T: Int>(lhs: T, rhs: T) -> T {
+            lhs.$checked(rhs).unwrap_or_else(|| overflow($sym, lhs, rhs))
+        }
+
+        #[cfg(not(feature = "checked"))]
+        pub fn $name<T: Int>(lhs: T, rhs: T) -> T {
+            lhs $op rhs
+        }
+    };
+}
+
+op!(add, checked_add, "+", +);
+op!(sub, checked_sub, "-", -);
+op!(mul, checked_mul, "*", *);
+
+// i32::MIN has no positive counterpart
+pub fn abs<T: Int>(v: T) -> T {
+    if v < T::ZERO {
+        sub(T::ZERO, v)
+    } else {
+        v
+    }
+}
+
+pub fn sum<T: Int>(iter: impl IntoIterator<Item = T>) -> T {
+    iter.into_iter().fold(T::ZERO, add)
+}
+
+pub fn product<T: Int>(iter: impl IntoIterator<Item = T>) -> T {
+    iter.into_iter().fold(T::ONE, mul)
+}
@@ -0,0 +1,199 @@
+use std::{fmt::Display, str::FromStr};
+
+const BYTE: usize = u8::BITS as usize;
+const MAX_WIDTH: usize = u64::BITS as usize;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BitError {
+    // more bits than a read or a write can take at once
+    TooWide {
+        amount: usize,
+        max: usize,
+    },
+    OutOfBounds {
+        pos: usize,
+        amount: usize,
+        len: usize,
+    },
+    BadHex(String),
+}
+
+impl Display for BitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooWide { amount, max } => {
+                write!(f, "{} bits don't fit in {}", amount, max)
+            }
+            Self::OutOfBounds { pos, amount, len } => write!(
+                f,
+                "can't read {} bits at {}, only {} bits in total",
+                amount, pos, len
+            ),
+            Self::BadHex(s) => write!(f, "not a hex string: {:?}", s),
+        }
+    }
+}
+
+pub struct BitReader {
+    b: Vec<u8>,
+    curr: usize,
+    len: usize,
+}
+
+impl BitReader {
+    pub fn new(b: Vec<u8>) -> Self {
+        let len = b.len() * BYTE;
+        BitReader { b, curr: 0, len }
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, BitError> {
+        s.parse()
+    }
+
+    pub fn curr(&self) -> usize {
+        self.curr
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.len - self.curr
+    }
+
+    pub fn peek(&self, amount: usize) -> Result<u64, BitError> {
+        if amount > MAX_WIDTH {
+            return Err(BitError::TooWide {
+                amount,
+                max: MAX_WIDTH,
+            });
+        }
+        if amount > self.remaining() {
+            return Err(BitError::OutOfBounds {
+                pos: self.curr,
+                amount,
+                len: self.len,
+            });
+        }
+
+        let mut res = 0;
+        let mut pos = self.curr;
+        let end = self.curr + amount;
+        while pos < end {
+            let (byte_i, bit_i) = (pos / BYTE, pos % BYTE);
+            let take = (BYTE - bit_i).min(end - pos);
+            let chunk = (self.b[byte_i] << bit_i) >> (BYTE - take);
+            res = (res << take) | chunk as u64;
+            pos += take;
+        }
+        Ok(res)
+    }
+
+    pub fn read(&mut self, amount: usize) -> Result<u64, BitError> {
+        let res = self.peek(amount)?;
+        self.curr += amount;
+        Ok(res)
+    }
+
+    pub fn skip(&mut self, amount: usize) -> Result<(), BitError> {
+        if amount > self.remaining() {
+            return Err(BitError::OutOfBounds {
+                pos: self.curr,
+                amount,
+                len: self.len,
+            });
+        }
+        self.curr += amount;
+        Ok(())
+    }
+}
+
+impl FromStr for BitReader {
+    type Err = BitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(BitError::BadHex(String::from(s)));
+        }
+        let bytes: Vec<u8> = (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect();
+
+        Ok(BitReader::new(bytes))
+    }
+}
+
+impl From<BitWriter> for BitReader {
+    fn from(w: BitWriter) -> Self {
+        BitReader {
+            b: w.b,
+            curr: 0,
+            len: w.len,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BitWriter {
+    b: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // the value has to fit in amount bits, nothing gets cut off
+    pub fn write(&mut self, value: u64, amount: usize) -> Result<(), BitError> {
+        if amount > MAX_WIDTH {
+            return Err(BitError::TooWide {
+                amount,
+                max: MAX_WIDTH,
+            });
+        }
+        let width = MAX_WIDTH - value.leading_zeros() as usize;
+        if width > amount {
+            return Err(BitError::TooWide {
+                amount: width,
+                max: amount,
+            });
+        }
+
+        let mut left = amount;
+        while left > 0 {
+            let bit_i = self.len % BYTE;
+            if bit_i == 0 {
+                self.b.push(0);
+            }
+            let take = (BYTE - bit_i).min(left);
+            let chunk = ((value >> (left - take)) & ((1 << take) - 1)) as u8;
+            *self.b.last_mut().unwrap() |= chunk << (BYTE - bit_i - take);
+            self.len += take;
+            left -= take;
+        }
+        Ok(())
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.b
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.b.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+}
@@ -0,0 +1,80 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::answer::Answer;
+
+pub const DEFAULT_PATH: &str = "cache/results.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResult {
+    pub answer: Answer,
+    // how long it took when it was computed
+    pub time_ms: f64,
+}
+
+pub fn input_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn key(day: u32, part: u8, version: &str, text: &str) -> String {
+    format!("day{:02}.{}.v{}.{}", day, part, version, input_hash(text))
+}
+
+// answers stay valid as long as both the input and the day's VERSION are the same
+pub struct ResultCache {
+    path: PathBuf,
+    results: BTreeMap<String, CachedResult>,
+}
+
+impl ResultCache {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let results = match fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(ResultCache { path, results })
+    }
+
+    pub fn get(&self, day: u32, part: u8, version: &str, text: &str) -> Option<&CachedResult> {
+        self.results.get(&key(day, part, version, text))
+    }
+
+    pub fn insert(
+        &mut self,
+        day: u32,
+        part: u8,
+        version: &str,
+        text: &str,
+        answer: Answer,
+        elapsed: Duration,
+    ) {
+        let result = CachedResult {
+            answer,
+            time_ms: elapsed.as_secs_f64() * 1000.0,
+        };
+        self.results.insert(key(day, part, version, text), result);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            &self.path,
+            serde_json::to_string_pretty(&self.results).unwrap(),
+        )
+    }
+}
@@ -1,10 +1,238 @@
-pub fn fetch(url: &str) -> String {
-    let client = reqwest::blocking::Client::new();
-    return client
-        .get(url)
-        .header(reqwest::header::COOKIE, "session=")
-        .send()
-        .unwrap()
-        .text()
-        .unwrap();
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::blocking::RequestBuilder;
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+// points every request somewhere else, like the mock server
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// an email or a repository link, sent along so the site knows who to reach
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub base_url: String,
+    pub session: String,
+    pub user_agent: String,
+    pub connect_timeout: Duration,
+    pub timeout: Duration,
+    // between the start of one request and the next one
+    pub min_interval: Duration,
+    // extra attempts after a 5xx or a connection error, only connection errors for posts
+    pub retries: u32,
+    // doubled after every retry
+    pub backoff: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let contact = env::var(CONTACT_VAR).unwrap_or_else(|_| String::from("no contact set"));
+        Config {
+            base_url: env::var(BASE_URL_VAR).unwrap_or_else(|_| String::from(DEFAULT_BASE_URL)),
+            session: env::var(SESSION_VAR).unwrap_or_default(),
+            user_agent: format!(
+                "adventofcode-2021-rust/{} ({})",
+                env!("CARGO_PKG_VERSION"),
+                contact
+            ),
+            connect_timeout: Duration::from_secs(10),
+            timeout: Duration::from_secs(30),
+            min_interval: Duration::from_secs(1),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Http(reqwest::Error),
+    // the server kept failing after all the retries
+    Status(u16),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Http(e) => write!(f, "request failed: {}", e),
+            ClientError::Status(status) => write!(f, "server answered with {}", status),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+pub struct Client {
+    http: reqwest::blocking::Client,
+    config: Config,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let http = reqwest::blocking::Client::builder()
+            .user_agent(config.user_agent.clone())
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.timeout)
+            .build()
+            .unwrap();
+        Client {
+            http,
+            config,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn wait_turn(&self) {
+        let mut last = self.last_request.lock().unwrap();
+        if let Some(at) = *last {
+            let since = at.elapsed();
+            if since < self.config.min_interval {
+                thread::sleep(self.config.min_interval - since);
+            }
+        }
+        *last = Some(Instant::now());
+    }
+
+    // only server errors are worth another try, the body of anything else is returned
+    // as is since the site explains most problems in the page itself, a request that
+    // isn't idempotent is only retried when it never reached the server
+    fn send(
+        &self,
+        session: &str,
+        idempotent: bool,
+        request: impl Fn() -> RequestBuilder,
+    ) -> Result<String, ClientError> {
+        let mut backoff = self.config.backoff;
+        let mut attempt = 0;
+        loop {
+            self.wait_turn();
+            let res = request()
+                .header(reqwest::header::COOKIE, format!("session={}", session))
+                .send();
+            let retry = match &res {
+                Ok(response) => idempotent && response.status().is_server_error(),
+                Err(e) => e.is_connect() || (idempotent && e.is_timeout()),
+            };
+            if !retry || attempt == self.config.retries {
+                return match res {
+                    Ok(response) if response.status().is_server_error() => {
+                        Err(ClientError::Status(response.status().as_u16()))
+                    }
+                    Ok(response) => Ok(response.text()?),
+                    Err(e) => Err(e.into()),
+                };
+            }
+            attempt += 1;
+            thread::sleep(backoff);
+            backoff *= 2;
+        }
+    }
+
+    // urls of the real site are moved over to the configured base url
+    pub fn resolve(&self, url: &str) -> String {
+        match url.strip_prefix(DEFAULT_BASE_URL) {
+            Some(path) => format!("{}{}", self.config.base_url.trim_end_matches('/'), path),
+            None => String::from(url),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Result<String, ClientError> {
+        self.get_as(url, &self.config.session)
+    }
+
+    // same as get but for another account, the rate limit still covers both
+    pub fn get_as(&self, url: &str, session: &str) -> Result<String, ClientError> {
+        let url = self.resolve(url);
+        self.send(session, true, || self.http.get(&url))
+    }
+
+    // a retried answer could count twice against the submission limits
+    pub fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        let url = self.resolve(url);
+        self.send(&self.config.session, false, || {
+            self.http.post(&url).form(form)
+        })
+    }
+}
+
+static SHARED: OnceLock<Client> = OnceLock::new();
+
+// every request of the process goes through this one so the interval holds across calls
+pub fn shared() -> &'static Client {
+    SHARED.get_or_init(|| Client::new(Config::default()))
+}
+
+pub fn fetch(url: &str) -> Result<String, ClientError> {
+    shared().get(url)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    // "too high" or "too low" when the site tells
+    Wrong(Option<String>),
+    TooSoon(String),
+    AlreadySolved,
+    Unexpected(String),
+}
+
+impl SubmitOutcome {
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if page.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .iter()
+                .find(|h| page.contains(*h))
+                .map(|h| String::from(*h));
+            SubmitOutcome::Wrong(hint)
+        } else if page.contains("You gave an answer too recently") {
+            let wait = page
+                .split("You have ")
+                .nth(1)
+                .and_then(|s| s.split(" left to wait").next())
+                .unwrap_or("some time");
+            SubmitOutcome::TooSoon(String::from(wait))
+        } else if page.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::AlreadySolved
+        } else {
+            SubmitOutcome::Unexpected(page.chars().take(200).collect())
+        }
+    }
+}
+
+pub fn puzzle_url(year: u32, day: u32) -> String {
+    format!("{}/{}/day/{}", DEFAULT_BASE_URL, year, day)
+}
+
+// "--- Day 7: The Treachery of Whales ---" from the puzzle page
+pub fn puzzle_title(page: &str) -> Option<String> {
+    let heading = page.split("<h2>").nth(1)?.split("</h2>").next()?;
+    let title = heading.trim_matches(|c: char| c == '-' || c.is_whitespace());
+    let title = title.split_once(": ").map_or(title, |(_, title)| title);
+    Some(String::from(title))
+}
+
+pub fn submit(url: &str, level: u8, answer: &str) -> Result<SubmitOutcome, ClientError> {
+    let level = level.to_string();
+    let page = shared().post_form(url, &[("level", &level), ("answer", answer)])?;
+    Ok(SubmitOutcome::parse(&page))
 }
@@ -0,0 +1,145 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::{
+    known::KnownAnswers,
+    report::{Entry, Report, Status},
+    runner,
+};
+
+const STYLE: &str = "
+body { background: #0f0f23; color: #cccccc; font-family: monospace; margin: 2em; }
+a { color: #009900; }
+h1 { color: #00cc00; }
+.calendar { display: grid; grid-template-columns: repeat(5, 1fr); gap: 1em; }
+.day { border: 1px solid #333340; padding: 0.8em; }
+.day h2 { margin: 0 0 0.5em 0; font-size: 1.1em; }
+.stars { color: #ffff66; }
+.missing { color: #333340; }
+.part { margin: 0.4em 0; }
+.answer { color: #ffffff; white-space: pre; }
+.failed { color: #ff6666; }
+.bar { background: #009900; height: 0.4em; margin-top: 0.2em; }
+img { max-width: 100%; image-rendering: pixelated; margin-top: 0.5em; }
+";
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// the Markdown copy if there's one next to the page, the site otherwise
+fn puzzle_link(day: u32, page_dir: &Path) -> String {
+    let local = format!("puzzles/day{:02}.md", day);
+    if page_dir.join(&local).exists() {
+        local
+    } else {
+        format!("https://adventofcode.com/2021/day/{}", day)
+    }
+}
+
+fn has_star(entry: &Entry, known: &KnownAnswers) -> bool {
+    if entry.status != Status::Ok {
+        return false;
+    }
+    match (known.get(entry.day, entry.part), &entry.answer) {
+        // art can't be compared, it's solved once someone has read and stored it
+        (Some(_), Some(answer)) if answer.contains('\n') => true,
+        (Some(known), Some(answer)) => known == answer,
+        _ => false,
+    }
+}
+
+fn write_part(html: &mut String, entry: &Entry, max_time: f64) {
+    write!(html, "<div class=\"part\">part {}: ", entry.part).unwrap();
+    match (&entry.answer, &entry.error) {
+        (Some(answer), _) => {
+            write!(html, "<span class=\"answer\">{}</span>", escape(answer)).unwrap()
+        }
+        (None, Some(error)) => {
+            write!(html, "<span class=\"failed\">{}</span>", escape(error)).unwrap()
+        }
+        (None, None) => (),
+    }
+    if let Some(time) = entry.time_ms {
+        let width = if max_time > 0.0 {
+            time / max_time * 100.0
+        } else {
+            0.0
+        };
+        write!(
+            html,
+            "<br>{:.3}ms<div class=\"bar\" style=\"width: {:.1}%\"></div>",
+            time,
+            width.max(0.5)
+        )
+        .unwrap();
+    }
+    html.push_str("</div>\n");
+}
+
+// images are (day, png bytes), they get inlined so the page is a single file,
+// page_dir is where it gets written, links to local copies are relative to it
+pub fn render(
+    report: &Report,
+    known: &KnownAnswers,
+    images: &[(u32, Vec<u8>)],
+    page_dir: &Path,
+) -> String {
+    let mut by_day: BTreeMap<u32, Vec<&Entry>> = BTreeMap::new();
+    for entry in report.entries.iter() {
+        by_day.entry(entry.day).or_default().push(entry);
+    }
+    let max_time = report
+        .entries
+        .iter()
+        .filter_map(|e| e.time_ms)
+        .fold(0.0, f64::max);
+    let total_stars = report.entries.iter().filter(|e| has_star(e, known)).count();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Advent of Code 2021</title>\n");
+    write!(html, "<style>{}</style>\n</head>\n<body>\n", STYLE).unwrap();
+    write!(
+        html,
+        "<h1>Advent of Code 2021</h1>\n<p><span class=\"stars\">{}*</span> from the {} report</p>\n",
+        total_stars,
+        escape(&report.command)
+    )
+    .unwrap();
+
+    html.push_str("<div class=\"calendar\">\n");
+    for day in 1..=25 {
+        let entries = by_day.get(&day).map(Vec::as_slice).unwrap_or(&[]);
+        let stars = entries.iter().filter(|e| has_star(e, known)).count();
+        write!(
+            html,
+            "<div class=\"day\">\n<h2><a href=\"{}\">Day {}: {}</a> <span class=\"stars\">{}</span><span class=\"missing\">{}</span></h2>\n",
+            puzzle_link(day, page_dir),
+            day,
+            escape(runner::find(day).map_or("", |d| d.title)),
+            "*".repeat(stars),
+            "*".repeat(2usize.saturating_sub(stars))
+        )
+        .unwrap();
+        for entry in entries {
+            write_part(&mut html, entry, max_time);
+        }
+        for (_, png) in images.iter().filter(|(d, _)| *d == day) {
+            writeln!(
+                html,
+                "<img alt=\"day {}\" src=\"data:image/png;base64,{}\">",
+                day,
+                BASE64.encode(png)
+            )
+            .unwrap();
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
@@ -1,5 +1,7 @@
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/1/input";
+pub const TITLE: &str = "Sonar Sweep";
+pub const VERSION: &str = "1";
 
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> u32 {
@@ -1,14 +1,17 @@
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/2/input";
+pub const TITLE: &str = "Dive!";
+pub const VERSION: &str = "1";
 
-enum Move {
+/// A submarine command, None for the ones it doesn't know.
+pub enum Move {
     Forward(i32),
     Down(i32),
     Up(i32),
     None(String),
 }
 
-fn get_move_vec(text: &str) -> Vec<Move> {
+pub fn get_move_vec(text: &str) -> Vec<Move> {
     text.lines()
         .map(|x| {
             let mut split = x.trim().split_whitespace();
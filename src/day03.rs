@@ -1,5 +1,7 @@
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/3/input";
+pub const TITLE: &str = "Binary Diagnostic";
+pub const VERSION: &str = "1";
 
 enum BitFilter {
     Majority,
@@ -2,17 +2,21 @@ use std::collections::HashSet;
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/4/input";
+pub const TITLE: &str = "Giant Squid";
+pub const VERSION: &str = "1";
 
 const BOARD_SIZE: usize = 5;
 
-struct Board {
+/// A bingo board, a number is crossed out by taking it out of its row and column.
+pub struct Board {
     rows: Vec<HashSet<u32>>,
     cols: Vec<HashSet<u32>>,
     is_winner: bool,
 }
 
 impl Board {
-    fn from_lines<'a, T>(lines: &mut T) -> Board
+    /// Reads the board from the next five lines.
+    pub fn from_lines<'a, T>(lines: &mut T) -> Board
     where
         T: Iterator<Item = &'a str>,
     {
@@ -35,7 +39,8 @@ impl Board {
         }
     }
 
-    fn cross_out(&mut self, num: u32) -> bool {
+    /// Crosses the number out, true once a row or column is complete.
+    pub fn cross_out(&mut self, num: u32) -> bool {
         for row in self.rows.iter_mut() {
             row.remove(&num);
             if row.is_empty() {
@@ -54,12 +59,18 @@ impl Board {
         false
     }
 
-    fn sum(&self) -> u32 {
+    pub fn is_winner(&self) -> bool {
+        self.is_winner
+    }
+
+    /// The numbers not crossed out yet added up.
+    pub fn sum(&self) -> u32 {
         self.rows.iter().flatten().sum()
     }
 }
 
-fn get_inputs(text: &str) -> (Vec<u32>, Vec<Board>) {
+/// The drawn numbers and the boards.
+pub fn get_inputs(text: &str) -> (Vec<u32>, Vec<Board>) {
     let mut lines = text.lines();
     let drawn_nums: Vec<u32> = lines
         .next()
@@ -75,7 +86,8 @@ fn get_inputs(text: &str) -> (Vec<u32>, Vec<Board>) {
     return (drawn_nums, boards);
 }
 
-fn play_game1(drawn_nums: Vec<u32>, boards: &mut Vec<Board>) -> Option<(u32, usize)> {
+/// The number that makes the first board win and the board's index.
+pub fn play_game1(drawn_nums: Vec<u32>, boards: &mut [Board]) -> Option<(u32, usize)> {
     for num in drawn_nums {
         for (i, board) in boards.iter_mut().enumerate() {
             let is_winner = board.cross_out(num);
@@ -96,7 +108,8 @@ pub fn solve1(text: &str) -> u32 {
     winner_num * winner_board.sum()
 }
 
-fn play_game2(drawn_nums: Vec<u32>, boards: &mut Vec<Board>) -> (u32, usize) {
+/// The number that makes the last board win and the board's index.
+pub fn play_game2(drawn_nums: Vec<u32>, boards: &mut [Board]) -> (u32, usize) {
     let boards_len = boards.len();
     let mut winners = Vec::new();
     for num in drawn_nums {
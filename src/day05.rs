@@ -1,7 +1,11 @@
-use std::{collections::HashMap, num::ParseIntError, str::FromStr};
+use std::{collections::HashMap, str::FromStr};
+
+use adventofcode_derive::ParseLine;
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/5/input";
+pub const TITLE: &str = "Hydrothermal Venture";
+pub const VERSION: &str = "1";
 
 fn get_change_rates(start: &Point, end: &Point) -> (i32, i32) {
     let x_diff = end.0 - start.0;
@@ -12,23 +16,20 @@ fn get_change_rates(start: &Point, end: &Point) -> (i32, i32) {
     (x_diff.signum(), y_diff.signum())
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Point(i32, i32);
-
-impl FromStr for Point {
-    type Err = ParseIntError;
+#[derive(Debug, PartialEq, Eq, Hash, ParseLine)]
+#[pattern("{0},{1}")]
+pub struct Point(pub i32, pub i32);
 
-    fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut coords = text.split(',');
-        let (x, y) = (coords.next(), coords.next());
-        let x = x.unwrap().parse()?;
-        let y = y.unwrap().parse()?;
-        Ok(Point(x, y))
-    }
+#[derive(Debug, ParseLine)]
+#[pattern("{start} -> {end}")]
+struct Vent {
+    start: Point,
+    end: Point,
 }
 
+/// A line of vents, parsed from `x1,y1 -> x2,y2` and iterated over point by point.
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     start: Point,
     end: Point,
     i: u32,
@@ -37,7 +38,8 @@ struct Line {
 }
 
 impl Line {
-    fn is_grid(&self) -> bool {
+    /// Whether the line is horizontal or vertical.
+    pub fn is_grid(&self) -> bool {
         self.dir_x == 0 || self.dir_y == 0
     }
 }
@@ -59,12 +61,10 @@ impl Iterator for Line {
 }
 
 impl FromStr for Line {
-    type Err = ParseIntError;
+    type Err = String;
 
     fn from_str(text: &str) -> Result<Self, Self::Err> {
-        let mut points = text.split(" -> ").take(2);
-        let start = points.next().unwrap().parse()?;
-        let end = points.next().unwrap().parse()?;
+        let Vent { start, end } = text.parse()?;
         let (rate_x, rate_y) = get_change_rates(&start, &end);
         Ok(Line {
             start,
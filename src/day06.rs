@@ -1,5 +1,28 @@
+use crate::{
+    arith,
+    params::{Param, Params},
+};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/6/input";
+pub const TITLE: &str = "Lanternfish";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "days1",
+        parts: &[1],
+        default: 80,
+        min: 0,
+        max: 400,
+    },
+    Param {
+        name: "days2",
+        parts: &[2],
+        default: 256,
+        min: 0,
+        max: 400,
+    },
+];
 
 const RESET_DAYS: usize = 7;
 const MAX_DAYS: usize = 9;
@@ -17,29 +40,35 @@ fn init_population(text: &str, population: &mut [u64]) {
 fn another_day(population: &mut [u64]) {
     let gonna_bear = population[0];
     population.rotate_left(1);
-    population[RESET_DAYS - 1] += gonna_bear;
+    population[RESET_DAYS - 1] = arith::add(population[RESET_DAYS - 1], gonna_bear);
 }
 
-#[allow(dead_code)]
-pub fn solve1(text: &str) -> u64 {
+/// How many lanternfish there are after the days, starting from the listed timers.
+pub fn population_after(text: &str, days: usize) -> u64 {
     let mut population = [0 as u64; MAX_DAYS];
     init_population(text, &mut population);
 
-    for _ in 0..80 {
+    for _ in 0..days {
         another_day(&mut population);
     }
 
-    population.iter().sum()
+    arith::sum(population)
 }
 
 #[allow(dead_code)]
-pub fn solve2(text: &str) -> u64 {
-    let mut population = [0 as u64; MAX_DAYS];
-    init_population(text, &mut population);
+pub fn solve1(text: &str) -> u64 {
+    solve1_with(text, &Params::new())
+}
 
-    for _ in 0..256 {
-        another_day(&mut population);
-    }
+pub fn solve1_with(text: &str, params: &Params) -> u64 {
+    population_after(text, params.get(PARAMS, "days1") as usize)
+}
+
+#[allow(dead_code)]
+pub fn solve2(text: &str) -> u64 {
+    solve2_with(text, &Params::new())
+}
 
-    population.iter().sum()
+pub fn solve2_with(text: &str, params: &Params) -> u64 {
+    population_after(text, params.get(PARAMS, "days2") as usize)
 }
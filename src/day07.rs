@@ -1,15 +1,28 @@
+use crate::{answer::Answer, arith};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/7/input";
+pub const TITLE: &str = "The Treachery of Whales";
+pub const VERSION: &str = "1";
+
+/// The fuel crabs at these distances spend when every step costs one more than the last.
+pub fn get_fuel_cost(d: &[i32]) -> i32 {
+    arith::sum(d.iter().map(|&d| {
+        let d = arith::abs(d);
+        arith::mul(d, arith::add(d, 1)) / 2
+    }))
+}
 
-fn get_fuel_cost(d: &[i32]) -> i32 {
-    d.iter().map(|d| (d.abs() * (d.abs() + 1)) / 2).sum()
+/// The fuel crabs at these distances spend at one per step.
+pub fn get_linear_fuel_cost(d: &[i32]) -> i32 {
+    arith::sum(d.iter().map(|&d| arith::abs(d)))
 }
 
 fn min_fuel_cost_recursive(
     dest: usize,
     distances: Vec<i32>,
     fuel_cost: i32,
-    total: usize,
+    cost: fn(&[i32]) -> i32,
     max_position: usize,
 ) -> (usize, i32) {
     if dest == 0 || dest == max_position {
@@ -17,17 +30,17 @@ fn min_fuel_cost_recursive(
     }
 
     // try to decrease dest
-    let distances: Vec<i32> = distances.into_iter().map(|d| d + 1).collect();
-    let decr_fuel_cost = get_fuel_cost(&distances);
+    let distances: Vec<i32> = distances.into_iter().map(|d| arith::add(d, 1)).collect();
+    let decr_fuel_cost = cost(&distances);
     if decr_fuel_cost < fuel_cost {
-        return min_fuel_cost_recursive(dest - 1, distances, decr_fuel_cost, total, max_position);
+        return min_fuel_cost_recursive(dest - 1, distances, decr_fuel_cost, cost, max_position);
     }
 
     // try to increase dest
-    let distances: Vec<i32> = distances.iter().map(|&d| d - 2).collect();
-    let incr_fuel_cost = get_fuel_cost(&distances);
+    let distances: Vec<i32> = distances.iter().map(|&d| arith::sub(d, 2)).collect();
+    let incr_fuel_cost = cost(&distances);
     if incr_fuel_cost < fuel_cost {
-        return min_fuel_cost_recursive(dest + 1, distances, incr_fuel_cost, total, max_position);
+        return min_fuel_cost_recursive(dest + 1, distances, incr_fuel_cost, cost, max_position);
     }
     return (dest, fuel_cost);
 }
@@ -35,10 +48,11 @@ fn min_fuel_cost_recursive(
 fn min_fuel_cost(positions: &[usize], behind_or_at: &[usize], max_position: usize) -> (usize, i32) {
     let total = positions.len();
     let mut dest = positions[total / 2];
-    let mut fuel_cost = positions
-        .iter()
-        .map(|&p| (p as i32 - dest as i32).abs())
-        .sum();
+    let mut fuel_cost = arith::sum(
+        positions
+            .iter()
+            .map(|&p| arith::abs(arith::sub(p as i32, dest as i32))),
+    );
     loop {
         if dest == 0 || dest == max_position {
             break (dest, fuel_cost);
@@ -47,20 +61,20 @@ fn min_fuel_cost(positions: &[usize], behind_or_at: &[usize], max_position: usiz
         // try to decrease dest
         let behind = behind_or_at[dest - 1];
         let after = total - behind;
-        let fuel_diff = after as i32 - behind as i32;
+        let fuel_diff = arith::sub(after as i32, behind as i32);
         if fuel_diff < 0 {
             dest -= 1;
-            fuel_cost += fuel_diff;
+            fuel_cost = arith::add(fuel_cost, fuel_diff);
             continue;
         }
 
         // try to increase dest
         let behind = behind_or_at[dest];
         let after = total - behind;
-        let fuel_diff = behind as i32 - after as i32;
+        let fuel_diff = arith::sub(behind as i32, after as i32);
         if fuel_diff < 0 {
             dest += 1;
-            fuel_cost += fuel_diff;
+            fuel_cost = arith::add(fuel_cost, fuel_diff);
             continue;
         }
         break (dest, fuel_cost);
@@ -68,7 +82,7 @@ fn min_fuel_cost(positions: &[usize], behind_or_at: &[usize], max_position: usiz
 }
 
 #[allow(dead_code)]
-pub fn solve1(text: &str) -> String {
+pub fn solve1(text: &str) -> Answer {
     let mut positions: Vec<usize> = text.trim().split(',').map(|i| i.parse().unwrap()).collect();
 
     positions.sort_unstable();
@@ -86,22 +100,55 @@ pub fn solve1(text: &str) -> String {
     }
 
     let (dest, fuel_cost) = min_fuel_cost(&positions, &behind_or_at, max_position);
-    format!("({}, {})", dest, fuel_cost)
+    Answer::from(fuel_cost).with_details(format!("position {}", dest))
 }
 
-#[allow(dead_code)]
-pub fn solve2(text: &str) -> String {
+// walks from the median towards cheaper positions one step at a time
+fn solve_descent(text: &str, cost: fn(&[i32]) -> i32) -> Answer {
     let mut positions: Vec<usize> = text.trim().split(',').map(|i| i.parse().unwrap()).collect();
     positions.sort_unstable();
 
     let &max_position = positions.iter().max().unwrap();
 
-    let total = positions.len();
-    let dest = positions[total / 2];
-    let distances: Vec<i32> = positions.iter().map(|&p| p as i32 - dest as i32).collect();
+    let dest = positions[positions.len() / 2];
+    let distances: Vec<i32> = positions
+        .iter()
+        .map(|&p| arith::sub(p as i32, dest as i32))
+        .collect();
+
+    let fuel_cost = cost(&distances);
+    let (dest, fuel_cost) = min_fuel_cost_recursive(dest, distances, fuel_cost, cost, max_position);
+    Answer::from(fuel_cost).with_details(format!("position {}", dest))
+}
+
+// every position gets tried
+fn solve_brute_force(text: &str, cost: fn(&[i32]) -> i32) -> Answer {
+    let positions: Vec<i32> = text.trim().split(',').map(|i| i.parse().unwrap()).collect();
+    let &max_position = positions.iter().max().unwrap();
+
+    let (dest, fuel_cost) = (0..=max_position)
+        .map(|dest| {
+            let distances: Vec<i32> = positions.iter().map(|&p| arith::sub(p, dest)).collect();
+            (dest, cost(&distances))
+        })
+        .min_by_key(|&(_, fuel_cost)| fuel_cost)
+        .unwrap();
+    Answer::from(fuel_cost).with_details(format!("position {}", dest))
+}
+
+#[allow(dead_code)]
+pub fn solve2(text: &str) -> Answer {
+    solve_descent(text, get_fuel_cost)
+}
+
+pub fn solve1_descent(text: &str) -> Answer {
+    solve_descent(text, get_linear_fuel_cost)
+}
+
+pub fn solve1_brute_force(text: &str) -> Answer {
+    solve_brute_force(text, get_linear_fuel_cost)
+}
 
-    let fuel_cost = get_fuel_cost(&distances);
-    let (dest, fuel_cost) =
-        min_fuel_cost_recursive(dest, distances, fuel_cost, total, max_position);
-    format!("({}, {})", dest, fuel_cost)
+pub fn solve2_brute_force(text: &str) -> Answer {
+    solve_brute_force(text, get_fuel_cost)
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/8/input";
+pub const TITLE: &str = "Seven Segment Search";
+pub const VERSION: &str = "1";
 
 #[derive(Default)]
 struct Segments {
@@ -24,7 +26,8 @@ fn is_one_segment(n: u8) -> bool {
     format!("{:b}", n).chars().filter(|&c| c == '1').count() == 1
 }
 
-fn solve_for_line(inputs: Vec<&str>, outputs: Vec<&str>) -> usize {
+/// The number on a display, worked out from its ten unique patterns.
+pub fn solve_for_line(inputs: Vec<&str>, outputs: Vec<&str>) -> usize {
     let mut len6 = Vec::with_capacity(3);
 
     let mut s: Segments = Default::default();
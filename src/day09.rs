@@ -2,6 +2,8 @@ use std::collections::HashSet;
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/9/input";
+pub const TITLE: &str = "Smoke Basin";
+pub const VERSION: &str = "1";
 
 const ZERO_CODE: u8 = 0x30;
 
@@ -32,8 +34,9 @@ pub fn solve1(text: &str) -> u32 {
     sum
 }
 
+/// A spot on the heightmap by row and column.
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
-struct Point(usize, usize);
+pub struct Point(pub usize, pub usize);
 
 impl Point {
     fn neighbors(&self, last_point: &Point) -> Vec<Point> {
@@ -54,7 +57,8 @@ impl Point {
     }
 }
 
-fn populate_basin_from(
+/// Adds every spot of the basin p is in to basin, last_point is the bottom right corner.
+pub fn populate_basin_from(
     p: Point,
     basin: &mut HashSet<Point>,
     map: &Vec<Vec<u8>>,
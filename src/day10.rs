@@ -1,5 +1,7 @@
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/10/input";
+pub const TITLE: &str = "Syntax Scoring";
+pub const VERSION: &str = "1";
 
 const OPENING_BRACES: &str = "([{<";
 
@@ -23,7 +25,8 @@ fn bracket_value(closing: &char) -> u64 {
     }
 }
 
-fn first_corrupted(line: &str) -> Option<char> {
+/// The first closing bracket that doesn't match, if the line is corrupted.
+pub fn first_corrupted(line: &str) -> Option<char> {
     let mut stack = Vec::new();
     for c in line.chars() {
         if OPENING_BRACES.contains(c) {
@@ -53,7 +56,8 @@ pub fn solve1(text: &str) -> u32 {
         .sum()
 }
 
-fn closing_seq(line: &str) -> Option<Vec<char>> {
+/// The brackets that complete the line, none if it's corrupted.
+pub fn closing_seq(line: &str) -> Option<Vec<char>> {
     let mut stack = Vec::new();
     for c in line.chars() {
         if OPENING_BRACES.contains(c) {
@@ -73,7 +77,7 @@ fn closing_seq(line: &str) -> Option<Vec<char>> {
     Some(seq)
 }
 
-fn seq_score(seq: Vec<char>) -> u64 {
+pub fn seq_score(seq: Vec<char>) -> u64 {
     seq.iter().fold(0, |acc, c| acc * 5 + bracket_value(c))
 }
 
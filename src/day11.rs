@@ -1,7 +1,22 @@
+use crate::{
+    export::Rgb,
+    grid::Grid,
+    params::{Param, Params},
+    sim::Simulation,
+};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/11/input";
+pub const TITLE: &str = "Dumbo Octopus";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[Param {
+    name: "steps",
+    parts: &[1],
+    default: 100,
+    min: 0,
+    max: 1_000_000,
+}];
 
-const STEPS: usize = 100;
 const MAX_ENERGY: u32 = 9;
 
 #[derive(Debug)]
@@ -45,7 +60,8 @@ impl Octopus {
     }
 }
 
-fn proceed(energies: &mut Vec<Vec<u32>>) -> usize {
+/// One step of the octopuses' energy levels, with how many of them flashed.
+pub fn proceed(energies: &mut Vec<Vec<u32>>) -> usize {
     let mut flashed = 0;
     let last_octopus = Octopus(energies.len() - 1, energies.first().unwrap().len() - 1);
     let mut affected_octopuses = Octopus::get_all(&last_octopus);
@@ -74,23 +90,93 @@ fn proceed(energies: &mut Vec<Vec<u32>>) -> usize {
     flashed
 }
 
-fn get_inputs(text: &str) -> Vec<Vec<u32>> {
+/// The energy levels, row by row.
+pub fn get_inputs(text: &str) -> Vec<Vec<u32>> {
     text.lines()
         .map(|l| l.trim().chars().map(|c| c.to_digit(10).unwrap()).collect())
         .collect()
 }
 
+#[derive(Clone)]
+struct Octopuses {
+    energies: Vec<Vec<u32>>,
+    flashed: usize,
+    total_flashed: usize,
+}
+
+impl Simulation for Octopuses {
+    fn step(&mut self) -> bool {
+        // stop once all of them flash at the same time
+        if self.flashed == self.energies.len() * self.energies.first().unwrap().len() {
+            return false;
+        }
+        self.flashed = proceed(&mut self.energies);
+        self.total_flashed += self.flashed;
+        true
+    }
+
+    fn frame(&self) -> Grid<Rgb> {
+        let height = self.energies.len();
+        let width = self.energies.first().unwrap().len();
+        let cells = self
+            .energies
+            .iter()
+            .flatten()
+            .map(|&e| match e {
+                0 => [0xff, 0xff, 0xc0],
+                e => [0x0f, 0x0f + 8 * e as u8, 0x23 + 16 * e as u8],
+            })
+            .collect();
+        Grid::new(height, width, cells)
+    }
+
+    fn render(&self) -> String {
+        let mut res = String::new();
+        for row in self.energies.iter() {
+            for e in row {
+                res.push(char::from_digit(*e, 10).unwrap());
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("flashes", self.flashed as u64),
+            ("total flashes", self.total_flashed as u64),
+        ]
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+pub fn simulation(text: &str) -> impl Simulation {
+    Octopuses {
+        energies: get_inputs(text),
+        flashed: 0,
+        total_flashed: 0,
+    }
+}
+
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> usize {
+    solve1_with(text, &Params::new())
+}
+
+pub fn solve1_with(text: &str, params: &Params) -> usize {
     let mut grid = get_inputs(text);
 
     let mut total_flashed = 0;
-    for _ in 0..STEPS {
+    for _ in 0..params.get(PARAMS, "steps") {
         total_flashed += proceed(&mut grid);
     }
     total_flashed
 }
 
+// the step count doesn't matter here, it runs until they all flash at once
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> u64 {
     let mut grid: Vec<Vec<u32>> = get_inputs(text);
@@ -1,7 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::memo::Memo;
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/12/input";
+pub const TITLE: &str = "Passage Pathing";
+pub const VERSION: &str = "1";
 
 const START: &str = "start";
 const END: &str = "end";
@@ -10,7 +14,8 @@ fn is_small(cave: &str) -> bool {
     cave.chars().any(|c| c.is_lowercase())
 }
 
-fn get_inputs(text: &str) -> HashMap<&str, HashSet<&str>> {
+/// The caves each cave leads to, nothing leads back to start or out of end.
+pub fn get_inputs(text: &str) -> HashMap<&str, HashSet<&str>> {
     let mut caves = HashMap::new();
 
     for line in text.lines() {
@@ -27,104 +32,113 @@ fn get_inputs(text: &str) -> HashMap<&str, HashSet<&str>> {
     caves
 }
 
-#[allow(dead_code)]
-pub fn solve1(text: &str) -> usize {
-    let caves = get_inputs(text);
-
-    let mut stack = vec![vec![START]];
-    let mut visited: Vec<&str> = Vec::new();
-    let mut paths: Vec<Vec<&str>> = Vec::new();
-    while !stack.is_empty() {
-        let curr = match stack.last_mut().unwrap().pop() {
-            None => {
-                stack.pop();
-                visited.pop();
-                continue;
-            }
-            Some(v) => v,
-        };
-        if curr == END {
-            paths.push(visited.clone());
-            continue;
-        }
-        if let Some(next_caves) = caves.get(curr) {
-            visited.push(curr);
-            let next_stack: Vec<&str> = next_caves
-                .iter()
-                .filter(|c| !is_small(c) || !visited.contains(&c))
-                .map(|&c| c)
-                .collect();
-            stack.push(next_stack);
+/// The cave system, counting the paths from start to end through it.
+pub struct Caves<'a> {
+    links: HashMap<&'a str, HashSet<&'a str>>,
+    // a bit per small cave to keep visited ones in a single number
+    small: HashMap<&'a str, u64>,
+}
+
+type PathsMemo<'a> = Memo<(&'a str, u64, bool), usize>;
+
+impl<'a> Caves<'a> {
+    pub fn new(links: HashMap<&'a str, HashSet<&'a str>>) -> Self {
+        let small: HashMap<_, _> = links
+            .values()
+            .flatten()
+            .chain(links.keys())
+            .filter(|c| is_small(c))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, &c)| (c, 1 << i))
+            .collect();
+        assert!(small.len() <= u64::BITS as usize, "too many small caves");
+        Caves { links, small }
+    }
+
+    /// The paths from start to end that visit small caves once, one of them twice if
+    /// can_revisit.
+    pub fn paths(&self, can_revisit: bool) -> usize {
+        let mut memo = Memo::new();
+        let paths = self.count_paths(START, 0, can_revisit, &mut memo);
+        let stats = memo.stats();
+        tracing::debug!(target: "memo", hits = stats.hits, misses = stats.misses, "day12 {}", stats);
+        paths
+    }
+
+    fn count_paths(
+        &self,
+        cave: &'a str,
+        visited: u64,
+        can_revisit: bool,
+        memo: &mut PathsMemo<'a>,
+    ) -> usize {
+        if cave == END {
+            return 1;
         }
+        memo.get_or_compute((cave, visited, can_revisit), |memo| {
+            let visited = visited | self.small.get(cave).unwrap_or(&0);
+            let mut count = 0;
+            for &next in self.links.get(cave).into_iter().flatten() {
+                let bit = self.small.get(next).unwrap_or(&0);
+                if visited & bit == 0 {
+                    count += self.count_paths(next, visited, can_revisit, memo);
+                } else if can_revisit {
+                    count += self.count_paths(next, visited, false, memo);
+                }
+            }
+            count
+        })
     }
-    paths.iter().count()
+}
+
+#[allow(dead_code)]
+pub fn solve1(text: &str) -> usize {
+    Caves::new(get_inputs(text)).paths(false)
 }
 
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> usize {
-    let caves = get_inputs(text);
-
-    let mut stack = vec![vec![START]];
-    let mut visited: Vec<&str> = Vec::new();
-    let mut paths: Vec<Vec<&str>> = Vec::new();
-    let mut visited_small_twice = None;
-    while !stack.is_empty() {
-        let curr = match stack.last_mut().unwrap().pop() {
-            None => {
-                let prev = visited.pop();
-                if let Some((v, p)) = visited_small_twice.zip(prev) {
-                    if v == p {
-                        visited_small_twice = None;
-                    }
+    Caves::new(get_inputs(text)).paths(true)
+}
+
+// walks every path instead of counting them, the way it was first solved
+fn solve_recursive<'a>(
+    curr: &'a str,
+    caves: &HashMap<&'a str, HashSet<&'a str>>,
+    visited: Vec<&'a str>,
+    paths: &mut Vec<Vec<&'a str>>,
+    visited_small_twice: bool,
+) {
+    if curr == END {
+        paths.push(visited.clone());
+        return;
+    }
+    if let Some(next_caves) = caves.get(curr) {
+        let mut visited = visited.clone();
+        visited.push(curr);
+        for cave in next_caves {
+            let mut visited_small_twice = visited_small_twice;
+            if is_small(cave) && visited.contains(cave) {
+                if visited_small_twice {
+                    continue;
                 }
-                stack.pop();
-                continue;
+                visited_small_twice = true;
             }
-            Some(v) => v,
-        };
-        if curr == END {
-            paths.push(visited.clone());
-            continue;
-        }
-        if is_small(curr) && visited.contains(&curr) {
-            visited_small_twice = Some(curr);
-        }
-        if let Some(next_caves) = caves.get(curr) {
-            visited.push(curr);
-            let next_stack: Vec<&str> = next_caves
-                .iter()
-                .filter(|c| !is_small(c) || !visited.contains(&c) || visited_small_twice.is_none())
-                .map(|&c| c)
-                .collect();
-            stack.push(next_stack);
+            solve_recursive(cave, caves, visited.clone(), paths, visited_small_twice);
         }
     }
-    paths.iter().count()
 }
 
-// fn solve_recursive<'a>(
-//     curr: &'a str,
-//     caves: &HashMap<&'a str, HashSet<&'a str>>,
-//     visited: Vec<&'a str>,
-//     paths: &mut Vec<Vec<&'a str>>,
-//     visited_small_twice: bool,
-// ) {
-//     if curr == END {
-//         paths.push(visited.clone());
-//         return;
-//     }
-//     if let Some(next_caves) = caves.get(curr) {
-//         let mut visited = visited.clone();
-//         visited.push(curr);
-//         for cave in next_caves {
-//             let mut visited_small_twice = visited_small_twice;
-//             if is_small(cave) && visited.contains(cave) {
-//                 if visited_small_twice {
-//                     continue;
-//                 }
-//                 visited_small_twice = true;
-//             }
-//             solve_recursive(cave, caves, visited.clone(), paths, visited_small_twice);
-//         }
-//     }
-// }
+pub fn solve1_enumerate(text: &str) -> usize {
+    let mut paths = Vec::new();
+    solve_recursive(START, &get_inputs(text), Vec::new(), &mut paths, true);
+    paths.len()
+}
+
+pub fn solve2_enumerate(text: &str) -> usize {
+    let mut paths = Vec::new();
+    solve_recursive(START, &get_inputs(text), Vec::new(), &mut paths, false);
+    paths.len()
+}
@@ -1,18 +1,25 @@
 use std::collections::HashSet;
 
+use crate::{answer::Answer, grid::Grid};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/13/input";
+pub const TITLE: &str = "Transparent Origami";
+pub const VERSION: &str = "1";
 
+/// A fold along x=n or y=n.
 #[derive(Debug)]
-enum Fold {
+pub enum Fold {
     X(usize),
     Y(usize),
 }
 
+/// A dot at x, y.
 #[derive(Debug, PartialEq, Eq, Hash)]
-struct Point(usize, usize);
+pub struct Point(pub usize, pub usize);
 
-fn fold(coords: HashSet<Point>, fold: &Fold) -> HashSet<Point> {
+/// The dots after folding the paper once, overlapping ones merge.
+pub fn fold(coords: HashSet<Point>, fold: &Fold) -> HashSet<Point> {
     coords
         .iter()
         .map(|p| match fold {
@@ -23,7 +30,8 @@ fn fold(coords: HashSet<Point>, fold: &Fold) -> HashSet<Point> {
         .collect()
 }
 
-fn get_inputs(text: &str) -> (HashSet<Point>, Vec<Fold>) {
+/// The dots and the folds to do, in order.
+pub fn get_inputs(text: &str) -> (HashSet<Point>, Vec<Fold>) {
     let mut parts = text.split("\n\n");
     let coords: HashSet<Point> = parts
         .next()
@@ -61,31 +69,38 @@ pub fn solve1(text: &str) -> usize {
     fold(coords, folds.first().unwrap()).iter().count()
 }
 
-fn coords_to_str(coords: &HashSet<Point>) -> String {
+/// The dots on a grid just big enough for them.
+pub fn coords_to_grid(coords: &HashSet<Point>) -> Grid<bool> {
     let height = coords.iter().max_by_key(|p| p.1).unwrap().1 + 1;
     let width = coords.iter().max_by_key(|p| p.0).unwrap().0 + 1;
 
-    let mut res = String::new();
-    res.push('\n');
-    for i in 0..height {
-        for j in 0..width {
-            if coords.contains(&Point(j, i)) {
-                res.push('#');
-            } else {
-                res.push('.');
-            }
-        }
-        res.push('\n');
+    let mut grid = Grid::filled(height, width, false);
+    for p in coords {
+        grid[(p.1, p.0)] = true;
     }
-    res
+    grid
 }
 
-#[allow(dead_code)]
-pub fn solve2(text: &str) -> String {
+fn coords_to_str(coords: &HashSet<Point>) -> String {
+    let grid = coords_to_grid(coords).map(|&dot| if dot { '#' } else { '.' });
+    format!("\n{}", grid)
+}
+
+fn fold_all(text: &str) -> HashSet<Point> {
     let (mut coords, folds) = get_inputs(text);
 
     for f in folds {
         coords = fold(coords, &f);
     }
-    coords_to_str(&coords)
+    coords
+}
+
+/// The paper once every fold is done.
+pub fn paper(text: &str) -> Grid<bool> {
+    coords_to_grid(&fold_all(text))
+}
+
+#[allow(dead_code)]
+pub fn solve2(text: &str) -> Answer {
+    Answer::art(coords_to_str(&fold_all(text)))
 }
@@ -1,9 +1,42 @@
 use std::{collections::HashMap, hash::Hash};
 
+use adventofcode_derive::ParseLine;
+
+use crate::{
+    arith,
+    params::{Param, Params},
+};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/14/input";
+pub const TITLE: &str = "Extended Polymerization";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "steps1",
+        parts: &[1],
+        default: 10,
+        min: 0,
+        max: 55,
+    },
+    Param {
+        name: "steps2",
+        parts: &[2],
+        default: 40,
+        min: 0,
+        max: 55,
+    },
+];
 
-fn get_inputs(text: &str) -> (Vec<char>, HashMap<(char, char), char>) {
+#[derive(ParseLine)]
+#[pattern("{pair} -> {insertion}")]
+struct Rule {
+    pair: String,
+    insertion: char,
+}
+
+/// The polymer template and the element to insert between each pair.
+pub fn get_inputs(text: &str) -> (Vec<char>, HashMap<(char, char), char>) {
     let mut parts = text.split("\n\n");
     let template: Vec<char> = parts.next().unwrap().chars().collect();
     let rules: HashMap<(char, char), char> = parts
@@ -11,11 +44,10 @@ fn get_inputs(text: &str) -> (Vec<char>, HashMap<(char, char), char>) {
         .unwrap()
         .lines()
         .map(|l| {
-            let mut from_to = l.split(" -> ");
-            let mut from_iter = from_to.next().unwrap().chars();
+            let rule: Rule = l.parse().unwrap();
+            let mut from_iter = rule.pair.chars();
             let from = (from_iter.next().unwrap(), from_iter.next().unwrap());
-            let to = from_to.next().unwrap().chars().last().unwrap();
-            (from, to)
+            (from, rule.insertion)
         })
         .collect();
 
@@ -24,10 +56,11 @@ fn get_inputs(text: &str) -> (Vec<char>, HashMap<(char, char), char>) {
 
 fn add_to<K: Eq + Hash>(map: &mut HashMap<K, u64>, key: K, n: u64) {
     let e = map.entry(key).or_insert(0);
-    *e += n;
+    *e = arith::add(*e, n);
 }
 
-fn char_counts(pairs: &HashMap<(char, char), u64>, last_char: &char) -> HashMap<char, u64> {
+/// How many of each element the polymer with these pairs has, given its last one.
+pub fn char_counts(pairs: &HashMap<(char, char), u64>, last_char: &char) -> HashMap<char, u64> {
     let mut counts = HashMap::new();
 
     pairs.iter().for_each(|((ch, _), count)| {
@@ -38,10 +71,12 @@ fn char_counts(pairs: &HashMap<(char, char), u64>, last_char: &char) -> HashMap<
     counts
 }
 
-fn solve_for(text: &str, steps: usize) -> u64 {
-    let (template, rules) = get_inputs(text);
-    let last_char = template.last().unwrap();
-
+/// How many times each pair of neighbouring elements shows up after the steps.
+pub fn pairs_after(
+    template: &[char],
+    rules: &HashMap<(char, char), char>,
+    steps: usize,
+) -> HashMap<(char, char), u64> {
     let mut pairs = HashMap::new();
     template.windows(2).for_each(|chars| {
         add_to(&mut pairs, (chars[0], chars[1]), 1);
@@ -62,7 +97,14 @@ fn solve_for(text: &str, steps: usize) -> u64 {
             });
         pairs = new_pairs;
     }
+    pairs
+}
 
+fn solve_for(text: &str, steps: usize) -> u64 {
+    let (template, rules) = get_inputs(text);
+    let last_char = template.last().unwrap();
+
+    let pairs = pairs_after(&template, &rules, steps);
     let counts = char_counts(&pairs, last_char);
     let most_common = counts.iter().max_by_key(|e| e.1).unwrap();
     let least_common = counts.iter().min_by_key(|e| e.1).unwrap();
@@ -71,10 +113,18 @@ fn solve_for(text: &str, steps: usize) -> u64 {
 
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> u64 {
-    solve_for(text, 10)
+    solve1_with(text, &Params::new())
+}
+
+pub fn solve1_with(text: &str, params: &Params) -> u64 {
+    solve_for(text, params.get(PARAMS, "steps1") as usize)
 }
 
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> u64 {
-    solve_for(text, 40)
+    solve2_with(text, &Params::new())
+}
+
+pub fn solve2_with(text: &str, params: &Params) -> u64 {
+    solve_for(text, params.get(PARAMS, "steps2") as usize)
 }
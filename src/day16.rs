@@ -1,88 +1,66 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    arith,
+    bits::{BitError, BitReader},
+};
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/16/input";
+pub const TITLE: &str = "Packet Decoder";
+pub const VERSION: &str = "1";
 
-struct BitReader {
-    b: Vec<u8>,
-    curr: usize,
+/// What an operator packet does with the values of its subpackets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpType {
+    Sum,
+    Product,
+    Min,
+    Max,
+    Gt,
+    Lt,
+    Eq,
 }
 
-impl BitReader {
-    fn new(b: Vec<u8>) -> Self {
-        BitReader { b, curr: 0 }
-    }
-
-    fn curr(&self) -> usize {
-        self.curr
-    }
-
-    fn read(&mut self, amount: usize) -> u8 {
-        let bits = u8::BITS as usize;
-        assert!(amount <= bits);
-
-        let (byte_i, bit_i) = (self.curr / bits, self.curr % bits);
-
-        let res = if bit_i + amount <= bits {
-            (self.b[byte_i] << bit_i) >> (bits - amount)
-        } else {
-            ((self.b[byte_i] << bit_i) | (self.b[byte_i + 1] >> (bits - bit_i))) >> (bits - amount)
-        };
-        self.curr += amount;
-        res
-    }
-
-    fn read_u32(&mut self, amount: usize) -> u32 {
-        let bits = u8::BITS as usize;
-        assert!(amount <= u32::BITS as usize);
-
-        let (bytes, rem_bits) = (amount / bits, amount % bits);
+#[derive(Debug, PartialEq, Eq)]
+pub enum PacketError {
+    Bits(BitError),
+    // comparisons take exactly two subpackets, the others at least one
+    Operands { op: OpType, count: usize },
+}
 
-        let mut res = 0;
-        for _ in 0..bytes {
-            res = (res << 8) | (self.read(8) as u32);
+impl Display for PacketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bits(e) => e.fmt(f),
+            Self::Operands { op, count } => {
+                write!(f, "{:?} can't take {} subpackets", op, count)
+            }
         }
-        res = (res << rem_bits) | (self.read(rem_bits) as u32);
-        res
     }
 }
 
-impl FromStr for BitReader {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes: Vec<u8> = (0..s.len())
-            .step_by(2)
-            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
-            .collect();
-
-        Ok(BitReader::new(bytes))
+impl From<BitError> for PacketError {
+    fn from(e: BitError) -> Self {
+        Self::Bits(e)
     }
 }
 
-enum OpType {
-    Sum,
-    Product,
-    Min,
-    Max,
-    Gt,
-    Lt,
-    Eq,
-}
-
-enum Payload {
+/// A literal value or an operator over subpackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
     Literal(u64),
     Op(OpType, Vec<Packet>),
 }
 
 impl Payload {
-    fn from(typ: u8, b: &mut BitReader) -> Self {
-        match typ {
+    fn from(typ: u8, b: &mut BitReader) -> Result<Self, PacketError> {
+        let payload = match typ {
             4 => {
                 let mut value: u64 = 0;
                 loop {
-                    let group = b.read(5);
-                    value = (value << 4) | (group & 0x0F) as u64;
+                    let group = b.read(5)?;
+                    value = (value << 4) | (group & 0x0F);
                     if (group >> 4) == 0 {
                         break;
                     }
@@ -92,59 +70,72 @@ impl Payload {
             op_typ => {
                 let mut subpackets = Vec::new();
 
-                let len_type = b.read(1);
+                let len_type = b.read(1)?;
                 match len_type {
                     0 => {
-                        let len_in_bits = b.read_u32(15) as usize;
+                        let len_in_bits = b.read(15)? as usize;
                         let end = b.curr() + len_in_bits;
                         while b.curr() < end {
-                            subpackets.push(Packet::from(b));
+                            subpackets.push(Packet::from(b)?);
                         }
                     }
                     _ => {
-                        let len_in_packets = b.read_u32(11);
+                        let len_in_packets = b.read(11)?;
                         for _ in 0..len_in_packets {
-                            subpackets.push(Packet::from(b))
+                            subpackets.push(Packet::from(b)?)
                         }
                     }
                 }
-                match op_typ {
-                    0 => Self::Op(OpType::Sum, subpackets),
-                    1 => Self::Op(OpType::Product, subpackets),
-                    2 => Self::Op(OpType::Min, subpackets),
-                    3 => Self::Op(OpType::Max, subpackets),
-                    5 => Self::Op(OpType::Gt, subpackets),
-                    6 => Self::Op(OpType::Lt, subpackets),
-                    7 => Self::Op(OpType::Eq, subpackets),
+                let op = match op_typ {
+                    0 => OpType::Sum,
+                    1 => OpType::Product,
+                    2 => OpType::Min,
+                    3 => OpType::Max,
+                    5 => OpType::Gt,
+                    6 => OpType::Lt,
+                    7 => OpType::Eq,
                     _ => panic!("hmmm"),
+                };
+                let count = subpackets.len();
+                let fits = match op {
+                    OpType::Gt | OpType::Lt | OpType::Eq => count == 2,
+                    _ => count > 0,
+                };
+                if !fits {
+                    return Err(PacketError::Operands { op, count });
                 }
+                Self::Op(op, subpackets)
             }
-        }
+        };
+        Ok(payload)
     }
 }
 
-struct Packet {
-    version: u8,
-    payload: Payload,
+/// A BITS packet, parsed from its hex transmission with `str::parse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    pub version: u8,
+    pub payload: Payload,
 }
 
 impl Packet {
-    fn from(b: &mut BitReader) -> Self {
-        let header = b.read(6);
-
-        let version = header >> 3;
-        let typ = header & 0x07;
+    /// Reads one packet with all its subpackets, leaving the reader right after it.
+    pub fn from(b: &mut BitReader) -> Result<Self, PacketError> {
+        let version = b.read(3)? as u8;
+        let typ = b.read(3)? as u8;
 
-        let payload = Payload::from(typ, b);
-        Packet { version, payload }
+        let payload = Payload::from(typ, b)?;
+        Ok(Packet { version, payload })
     }
 
-    fn eval(&self) -> u64 {
+    /// The value of the expression the packet encodes, total since parsing
+    /// checks every operator's subpacket count.
+    pub fn eval(&self) -> u64 {
         match &self.payload {
             Payload::Literal(v) => *v,
             Payload::Op(op_type, subpackets) => match op_type {
-                OpType::Sum => subpackets.iter().fold(0, |acc, p| acc + p.eval()),
-                OpType::Product => subpackets.iter().fold(1, |acc, p| acc * p.eval()),
+                OpType::Sum => arith::sum(subpackets.iter().map(Packet::eval)),
+                OpType::Product => arith::product(subpackets.iter().map(Packet::eval)),
                 OpType::Min => subpackets.iter().map(|p| p.eval()).min().unwrap(),
                 OpType::Max => subpackets.iter().map(|p| p.eval()).max().unwrap(),
                 OpType::Gt => {
@@ -171,29 +162,37 @@ impl Packet {
             },
         }
     }
+
+    /// The versions of the packet and all its subpackets added up.
+    pub fn version_sum(&self) -> u64 {
+        let mut sum = 0;
+        let mut stack = vec![self];
+        while let Some(p) = stack.pop() {
+            sum += p.version as u64;
+            if let Payload::Op(_, packets) = &p.payload {
+                stack.extend(packets.iter());
+            }
+        }
+        sum
+    }
+}
+
+impl FromStr for Packet {
+    type Err = PacketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Packet::from(&mut BitReader::from_hex(s.trim())?)
+    }
 }
 
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> u64 {
-    let mut b: BitReader = text.trim().parse().unwrap();
-    let packet = Packet::from(&mut b);
-
-    let mut sum = 0;
-    let mut stack = vec![packet];
-    while !stack.is_empty() {
-        let p = stack.pop().unwrap();
-        sum += p.version as u64;
-        match p.payload {
-            Payload::Op(_, packets) => packets.into_iter().for_each(|p| stack.push(p)),
-            _ => (),
-        }
-    }
-    sum
+    let packet: Packet = text.parse().unwrap();
+    packet.version_sum()
 }
 
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> u64 {
-    let mut b: BitReader = text.trim().parse().unwrap();
-    let packet = Packet::from(&mut b);
+    let packet: Packet = text.parse().unwrap();
     packet.eval()
 }
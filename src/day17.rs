@@ -1,8 +1,13 @@
 use std::collections::HashSet;
 
+use adventofcode_derive::ParseLine;
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/17/input";
+pub const TITLE: &str = "Trick Shot";
+pub const VERSION: &str = "1";
 
+/// Whether a probe launched with this velocity is ever within the target after a step.
 pub fn launch((mut v_x, mut v_y): (i32, i32), target: ((i32, i32), (i32, i32))) -> bool {
     let (x_range, y_range) = target;
     let is_before = |x, y| x < x_range.0 || y > y_range.1;
@@ -20,21 +25,19 @@ pub fn launch((mut v_x, mut v_y): (i32, i32), target: ((i32, i32), (i32, i32)))
     !is_after(x, y)
 }
 
-fn get_inputs(text: &str) -> ((i32, i32), (i32, i32)) {
-    let mut parts = text.trim().trim_start_matches("target area: ").split(", ");
-    let mut x = parts.next().unwrap().trim_start_matches("x=").split("..");
-    let x_range = (
-        x.next().unwrap().parse().unwrap(),
-        x.next().unwrap().parse().unwrap(),
-    );
-
-    let mut y = parts.next().unwrap().trim_start_matches("y=").split("..");
-    let y_range = (
-        y.next().unwrap().parse().unwrap(),
-        y.next().unwrap().parse().unwrap(),
-    );
-
-    (x_range, y_range)
+#[derive(ParseLine)]
+#[pattern("target area: x={x_min}..{x_max}, y={y_min}..{y_max}")]
+struct Target {
+    x_min: i32,
+    x_max: i32,
+    y_min: i32,
+    y_max: i32,
+}
+
+/// The target area as its x and y ranges, both inclusive.
+pub fn get_inputs(text: &str) -> ((i32, i32), (i32, i32)) {
+    let target: Target = text.trim().parse().unwrap();
+    ((target.x_min, target.x_max), (target.y_min, target.y_max))
 }
 
 fn min_x_vel(x_range: (i32, i32)) -> i32 {
@@ -6,9 +6,13 @@ use std::{
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/18/input";
+pub const TITLE: &str = "Snailfish";
+pub const VERSION: &str = "1";
 
-#[derive(Clone)]
-enum Number {
+/// A snailfish number, parsed from and displayed in the puzzle's `[a,b]` notation.
+/// Adding two of them gives the reduced sum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Number {
     Simple(u32),
     Pair {
         left: Box<Number>,
@@ -39,7 +43,8 @@ impl Number {
         }
     }
 
-    fn reduce(&mut self) {
+    /// Explodes and splits until none of the two applies anymore.
+    pub fn reduce(&mut self) {
         let _ = self.explode(0);
 
         while self.split() {
@@ -87,7 +92,7 @@ impl Number {
         }
     }
 
-    fn magnitude(&self) -> u32 {
+    pub fn magnitude(&self) -> u32 {
         match self {
             Self::Simple(val) => *val,
             Self::Pair { left, right } => 3 * left.magnitude() + 2 * right.magnitude(),
@@ -98,18 +103,14 @@ impl Number {
 impl Add for Number {
     type Output = Self;
 
+    // a plain number on either side just becomes a half of the pair
     fn add(self, other: Self) -> Self::Output {
-        match &self {
-            Self::Simple(_) => panic!("Cant add to simple number"),
-            Self::Pair { .. } => {
-                let mut res = Number::Pair {
-                    left: Box::new(self),
-                    right: Box::new(other),
-                };
-                res.reduce();
-                res
-            }
-        }
+        let mut res = Number::Pair {
+            left: Box::new(self),
+            right: Box::new(other),
+        };
+        res.reduce();
+        res
     }
 }
 
@@ -118,7 +119,7 @@ impl Display for Number {
         match self {
             Self::Simple(n) => write!(f, "{}", n),
             Self::Pair { left, right } => {
-                write!(f, "[{}, {}]", left, right)
+                write!(f, "[{},{}]", left, right)
             }
         }
     }
@@ -128,9 +129,13 @@ fn parse_number(s: &mut Chars) -> Result<Number, String> {
     let c = s.next().ok_or("expected a char")?;
     if c == '[' {
         let left = parse_number(s)?;
-        assert_eq!(s.next().unwrap(), ',');
+        if s.next() != Some(',') {
+            return Err(String::from("expected a ','"));
+        }
         let right = parse_number(s)?;
-        assert_eq!(s.next().unwrap(), ']');
+        if s.next() != Some(']') {
+            return Err(String::from("expected a ']'"));
+        }
         return Ok(Number::Pair {
             left: Box::new(left),
             right: Box::new(right),
@@ -144,7 +149,12 @@ impl FromStr for Number {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_number(&mut s.chars())
+        let mut chars = s.chars();
+        let number = parse_number(&mut chars)?;
+        match chars.next() {
+            Some(c) => Err(format!("unexpected {:?} after the number", c)),
+            None => Ok(number),
+        }
     }
 }
 
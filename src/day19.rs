@@ -4,8 +4,22 @@ use std::{
     ops::{Add, Sub},
 };
 
+use crate::{
+    params::{Param, Params},
+    progress,
+};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/19/input";
+pub const TITLE: &str = "Beacon Scanner";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[Param {
+    name: "overlap",
+    parts: &[1, 2],
+    default: 12,
+    min: 3,
+    max: 26,
+}];
 
 fn cos_disc(n: u32) -> i32 {
     if n % 2 == 1 {
@@ -23,15 +37,18 @@ fn sin_disc(n: u32) -> i32 {
     cos_disc(n + 1)
 }
 
+/// A beacon or scanner position.
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct Point(i32, i32, i32);
+pub struct Point(pub i32, pub i32, pub i32);
 
 impl Point {
-    fn sum_abs(&self) -> i32 {
+    /// The manhattan distance to the origin.
+    pub fn sum_abs(&self) -> i32 {
         self.0.abs() + self.1.abs() + self.2.abs()
     }
 
-    fn rotate_x(&self, n: u32) -> Self {
+    /// Turned n quarter turns around the x axis.
+    pub fn rotate_x(&self, n: u32) -> Self {
         Point(
             self.0,
             cos_disc(n) * self.1 - sin_disc(n) * self.2,
@@ -39,7 +56,8 @@ impl Point {
         )
     }
 
-    fn rotate_y(&self, n: u32) -> Self {
+    /// Turned n quarter turns around the y axis.
+    pub fn rotate_y(&self, n: u32) -> Self {
         Point(
             cos_disc(n) * self.0 + sin_disc(n) * self.2,
             self.1,
@@ -47,7 +65,8 @@ impl Point {
         )
     }
 
-    fn rotate_z(&self, n: u32) -> Self {
+    /// Turned n quarter turns around the z axis.
+    pub fn rotate_z(&self, n: u32) -> Self {
         Point(
             cos_disc(n) * self.0 - sin_disc(n) * self.1,
             sin_disc(n) * self.0 + cos_disc(n) * self.1,
@@ -78,11 +97,13 @@ impl Display for Point {
     }
 }
 
+/// The beacons a scanner sees, relative to the scanner until it's aligned
+/// with scanner 0 by `adjust_scanners`, which also sets its position.
 #[derive(Clone, Debug)]
-struct Scanner {
-    id: usize,
-    points: HashSet<Point>,
-    pos: Point,
+pub struct Scanner {
+    pub id: usize,
+    pub points: HashSet<Point>,
+    pub pos: Point,
 }
 
 fn vectors(points: &HashSet<Point>) -> HashMap<Point, (Point, Point)> {
@@ -103,7 +124,13 @@ fn vectors(points: &HashSet<Point>) -> HashMap<Point, (Point, Point)> {
 }
 
 impl Scanner {
-    fn try_adjust_for(&self, base: &Scanner, threshold: usize) -> Option<(HashSet<Point>, Point)> {
+    /// The points turned and moved into the base's frame, with the scanner's position in it,
+    /// when at least threshold beacon pairs match.
+    pub fn try_adjust_for(
+        &self,
+        base: &Scanner,
+        threshold: usize,
+    ) -> Option<(HashSet<Point>, Point)> {
         let base_points = &base.points;
         let base_vectors = vectors(base_points);
         for rot_x in 0..4 {
@@ -150,7 +177,8 @@ impl Display for Scanner {
     }
 }
 
-fn get_inputs(text: &str) -> Vec<Scanner> {
+/// The scanner reports, none of them aligned yet.
+pub fn get_inputs(text: &str) -> Vec<Scanner> {
     text.split("\n\n")
         .enumerate()
         .map(|(id, scanner)| {
@@ -175,7 +203,9 @@ fn get_inputs(text: &str) -> Vec<Scanner> {
         .collect()
 }
 
-fn adjust_scanners(scanners: &mut Vec<Scanner>) {
+/// Aligns every scanner reachable from scanner 0 through overlaps of at least overlap beacons.
+pub fn adjust_scanners(scanners: &mut [Scanner], overlap: usize) {
+    let scanners_len = scanners.len();
     let mut stack = vec![scanners[0].clone()];
     let mut visited = HashSet::new();
     visited.insert(0);
@@ -186,13 +216,18 @@ fn adjust_scanners(scanners: &mut Vec<Scanner>) {
             if visited.contains(&s.id) {
                 continue;
             }
-            match s.try_adjust_for(&base_scanner, 12) {
+            match s.try_adjust_for(&base_scanner, overlap) {
                 None => continue,
                 Some((v, pos)) => {
                     s.points = v;
                     s.pos = pos;
                     stack.push(s.clone());
                     visited.insert(s.id);
+                    progress::report(
+                        "scanners aligned",
+                        visited.len() as u64,
+                        Some(scanners_len as u64),
+                    );
                 }
             }
         }
@@ -201,9 +236,13 @@ fn adjust_scanners(scanners: &mut Vec<Scanner>) {
 
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> usize {
+    solve1_with(text, &Params::new())
+}
+
+pub fn solve1_with(text: &str, params: &Params) -> usize {
     let mut scanners: Vec<Scanner> = get_inputs(text);
 
-    adjust_scanners(&mut scanners);
+    adjust_scanners(&mut scanners, params.get(PARAMS, "overlap") as usize);
 
     let all_points = scanners.into_iter().fold(HashSet::new(), |acc, s| {
         acc.union(&s.points).cloned().collect()
@@ -218,9 +257,13 @@ pub fn solve1(text: &str) -> usize {
 
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> i32 {
+    solve2_with(text, &Params::new())
+}
+
+pub fn solve2_with(text: &str, params: &Params) -> i32 {
     let mut scanners: Vec<Scanner> = get_inputs(text);
 
-    adjust_scanners(&mut scanners);
+    adjust_scanners(&mut scanners, params.get(PARAMS, "overlap") as usize);
     scanners
         .iter()
         .flat_map(|s1| {
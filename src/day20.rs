@@ -1,37 +1,65 @@
 use std::{fmt::Display, str::FromStr};
 
+use adventofcode_derive::CharEnum;
+
+use crate::{
+    export::{self, Palette, Rgb},
+    grid::Grid,
+    params::{Param, Params},
+    sim::Simulation,
+};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/20/input";
-
-struct Image {
-    pixels: Vec<Vec<Pixel>>,
+pub const TITLE: &str = "Trench Map";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "enhancements1",
+        parts: &[1],
+        default: 2,
+        min: 0,
+        max: 200,
+    },
+    Param {
+        name: "enhancements2",
+        parts: &[2],
+        default: 50,
+        min: 0,
+        max: 200,
+    },
+];
+
+/// The known part of an infinite image, every pixel outside of it is the same.
+#[derive(Clone)]
+pub struct Image {
+    pixels: Grid<Pixel>,
     outside: Pixel,
 }
 
 impl Image {
-    fn height(&self) -> usize {
-        self.pixels.len()
+    pub fn height(&self) -> usize {
+        self.pixels.height()
     }
 
-    fn width(&self) -> usize {
-        self.pixels.first().unwrap().len()
+    pub fn width(&self) -> usize {
+        self.pixels.width()
     }
 
-    fn get(&self, (i, j): (i32, i32)) -> Pixel {
+    /// The pixel at row i and column j, which can be outside of the known part.
+    pub fn get(&self, (i, j): (i32, i32)) -> Pixel {
         if i >= 0 && j >= 0 && i < self.height() as i32 && j < self.width() as i32 {
-            self.pixels[i as usize][j as usize]
+            self.pixels[(i as usize, j as usize)]
         } else {
             self.outside
         }
     }
 
-    fn count_lit(&self) -> usize {
+    pub fn count_lit(&self) -> usize {
         let mut count = 0;
-        for row in self.pixels.iter() {
-            for p in row {
-                if let Pixel::Light = p {
-                    count += 1;
-                }
+        for (_, p) in self.pixels.iter() {
+            if let Pixel::Light = p {
+                count += 1;
             }
         }
         count
@@ -54,13 +82,14 @@ impl Image {
         algorithm[num]
     }
 
-    fn enhance(&mut self, algorithm: &Vec<Pixel>) {
+    /// Runs the enhancement algorithm once, the known part grows by a pixel on every side.
+    pub fn enhance(&mut self, algorithm: &Vec<Pixel>) {
         let width = self.width();
         let height = self.height();
-        let mut new_pixels = vec![vec![Pixel::Dark; width + 2]; height + 2];
+        let mut new_pixels = Grid::filled(height + 2, width + 2, Pixel::Dark);
         for i in 0..height + 2 {
             for j in 0..width + 2 {
-                new_pixels[i][j] = self.enhanced((i as i32 - 1, j as i32 - 1), algorithm);
+                new_pixels[(i, j)] = self.enhanced((i as i32 - 1, j as i32 - 1), algorithm);
             }
         }
         self.outside = match self.outside {
@@ -72,10 +101,10 @@ impl Image {
 }
 
 impl FromStr for Image {
-    type Err = &'static str;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pixels = s.lines().map(line_to_pixels).collect();
+        let pixels = s.parse()?;
 
         Ok(Image {
             pixels,
@@ -86,20 +115,15 @@ impl FromStr for Image {
 
 impl Display for Image {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut res = String::new();
-        for row in self.pixels.iter() {
-            for p in row {
-                res.push_str(&format!("{}", p));
-            }
-            res.push('\n');
-        }
-        writeln!(f, "{}", res)
+        writeln!(f, "{}", self.pixels)
     }
 }
 
-#[derive(Clone, Copy)]
-enum Pixel {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CharEnum)]
+pub enum Pixel {
+    #[ch('#')]
     Light,
+    #[ch('.')]
     Dark,
 }
 
@@ -112,27 +136,21 @@ impl Pixel {
     }
 }
 
-impl Display for Pixel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ch = match self {
-            Self::Light => '#',
-            Self::Dark => '.',
-        };
-        write!(f, "{}", ch)
+impl Palette for Pixel {
+    fn rgb(&self) -> Rgb {
+        match self {
+            Pixel::Light => export::WHITE,
+            Pixel::Dark => export::BLACK,
+        }
     }
 }
 
 fn line_to_pixels(line: &str) -> Vec<Pixel> {
-    line.chars()
-        .map(|c| match c {
-            '#' => Pixel::Light,
-            '.' => Pixel::Dark,
-            _ => panic!("unexpected symbol {}", c),
-        })
-        .collect()
+    line.chars().map(|c| Pixel::try_from(c).unwrap()).collect()
 }
 
-fn get_inputs(text: &str) -> (Vec<Pixel>, Image) {
+/// The enhancement algorithm and the input image.
+pub fn get_inputs(text: &str) -> (Vec<Pixel>, Image) {
     let mut parts = text.split("\n\n");
     let algorithm = parts.next().map(line_to_pixels).unwrap();
 
@@ -141,21 +159,68 @@ fn get_inputs(text: &str) -> (Vec<Pixel>, Image) {
     (algorithm, image)
 }
 
+#[derive(Clone)]
+struct Enhancement {
+    algorithm: Vec<Pixel>,
+    image: Image,
+}
+
+impl Simulation for Enhancement {
+    fn step(&mut self) -> bool {
+        self.image.enhance(&self.algorithm);
+        true
+    }
+
+    fn frame(&self) -> Grid<Rgb> {
+        export::to_frame(&self.image.pixels)
+    }
+
+    fn render(&self) -> String {
+        self.image.to_string()
+    }
+
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("lit pixels", self.image.count_lit() as u64),
+            ("width", self.image.width() as u64),
+            ("height", self.image.height() as u64),
+        ]
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+pub fn simulation(text: &str) -> impl Simulation {
+    let (algorithm, image) = get_inputs(text);
+    Enhancement { algorithm, image }
+}
+
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> usize {
+    solve1_with(text, &Params::new())
+}
+
+pub fn solve1_with(text: &str, params: &Params) -> usize {
     let (algorithm, mut image) = get_inputs(text);
 
-    image.enhance(&algorithm);
-    image.enhance(&algorithm);
+    for _ in 0..params.get(PARAMS, "enhancements1") {
+        image.enhance(&algorithm);
+    }
 
     image.count_lit()
 }
 
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> usize {
+    solve2_with(text, &Params::new())
+}
+
+pub fn solve2_with(text: &str, params: &Params) -> usize {
     let (algorithm, mut image) = get_inputs(text);
 
-    for _ in 0..50 {
+    for _ in 0..params.get(PARAMS, "enhancements2") {
         image.enhance(&algorithm);
     }
 
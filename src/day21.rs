@@ -1,20 +1,44 @@
-use std::collections::HashMap;
+use crate::{
+    answer::Answer,
+    memo::Memo,
+    params::{Param, Params},
+};
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/21/input";
-
-#[derive(PartialEq, Eq, Hash, Clone)]
-struct Player {
-    pos: usize,
-    score: usize,
+pub const TITLE: &str = "Dirac Dice";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "target1",
+        parts: &[1],
+        default: 1000,
+        min: 1,
+        max: 100_000,
+    },
+    Param {
+        name: "target2",
+        parts: &[2],
+        default: 21,
+        min: 1,
+        max: 28,
+    },
+];
+
+/// A player on the circular track, pos counts from 0 so space 10 is 9.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Player {
+    pub pos: usize,
+    pub score: usize,
 }
 
 impl Player {
-    fn new(pos: usize) -> Self {
+    pub fn new(pos: usize) -> Self {
         Player { pos, score: 0 }
     }
 
-    fn go(&self, n: usize) -> Player {
+    /// The player after moving n spaces and scoring the space landed on.
+    pub fn go(&self, n: usize) -> Player {
         let target = (self.pos + n) % 10;
         Player {
             pos: target,
@@ -26,7 +50,8 @@ impl Player {
         self.go(die.take(n).sum::<usize>())
     }
 
-    fn roll_dirac(&self) -> Vec<Player> {
+    /// The player in each of the 27 universes three rolls of the Dirac die split into.
+    pub fn roll_dirac(&self) -> Vec<Player> {
         let mut new_players = Vec::new();
         for i in 1..=3 {
             for j in 1..=3 {
@@ -41,6 +66,11 @@ impl Player {
 
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> usize {
+    solve1_with(text, &Params::new())
+}
+
+pub fn solve1_with(text: &str, params: &Params) -> usize {
+    let target = params.get(PARAMS, "target1") as usize;
     let mut players = text.trim().lines().map(|l| {
         l.split_whitespace()
             .last()
@@ -58,13 +88,13 @@ pub fn solve1(text: &str) -> usize {
     let losing = loop {
         p1 = p1.roll(die, 3);
         roll_count += 3;
-        if p1.score >= 1000 {
+        if p1.score >= target {
             break p2.score;
         }
 
         p2 = p2.roll(die, 3);
         roll_count += 3;
-        if p2.score >= 1000 {
+        if p2.score >= target {
             break p1.score;
         }
     };
@@ -72,40 +102,41 @@ pub fn solve1(text: &str) -> usize {
     losing * roll_count
 }
 
-fn player_counters(
+/// In how many universes each player wins, first being the one to move.
+pub fn player_counters(
     first: &Player,
     second: &Player,
-    counters: &mut HashMap<(Player, Player), (u64, u64)>,
+    target: usize,
+    memo: &mut Memo<(Player, Player), (u64, u64)>,
 ) -> (u64, u64) {
-    let key = (first.clone(), second.clone());
-    if let Some(&v) = counters.get(&key) {
-        return v;
-    }
-
-    if first.score >= 21 {
-        counters.insert(key, (1, 0));
-        return (1, 0);
-    }
+    memo.get_or_compute((first.clone(), second.clone()), |memo| {
+        if first.score >= target {
+            return (1, 0);
+        }
 
-    if second.score >= 21 {
-        counters.insert(key, (0, 1));
-        return (0, 1);
-    }
+        if second.score >= target {
+            return (0, 1);
+        }
 
-    let mut counts = (0, 0);
+        let mut counts = (0, 0);
 
-    for p in first.roll_dirac() {
-        let next_counts = player_counters(second, &p, counters);
-        counts.0 += next_counts.1;
-        counts.1 += next_counts.0;
-    }
+        for p in first.roll_dirac() {
+            let next_counts = player_counters(second, &p, target, memo);
+            counts.0 += next_counts.1;
+            counts.1 += next_counts.0;
+        }
 
-    counters.insert(key, counts);
-    counts
+        counts
+    })
 }
 
 #[allow(dead_code)]
-pub fn solve2(text: &str) -> String {
+pub fn solve2(text: &str) -> Answer {
+    solve2_with(text, &Params::new())
+}
+
+pub fn solve2_with(text: &str, params: &Params) -> Answer {
+    let target = params.get(PARAMS, "target2") as usize;
     let mut players = text.trim().lines().map(|l| {
         l.split_whitespace()
             .last()
@@ -116,7 +147,13 @@ pub fn solve2(text: &str) -> String {
     let p1 = Player::new(players.next().unwrap() - 1);
     let p2 = Player::new(players.next().unwrap() - 1);
 
-    let (p1_wins, p2_wins) = player_counters(&p1, &p2, &mut HashMap::new());
+    let mut memo = Memo::new();
+    let (p1_wins, p2_wins) = player_counters(&p1, &p2, target, &mut memo);
 
-    format!("{} {}", p1_wins, p2_wins)
+    Answer::from(p1_wins.max(p2_wins)).with_details(format!(
+        "player 1 wins in {} universes, player 2 in {}, memo: {}",
+        p1_wins,
+        p2_wins,
+        memo.stats()
+    ))
 }
@@ -1,81 +1,56 @@
-use std::{cmp, num::ParseIntError, str::FromStr};
+use std::str::FromStr;
 
-#[allow(dead_code)]
-pub const URL: &str = "https://adventofcode.com/2021/day/22/input";
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Range(i32, i32);
-
-impl Range {
-    fn is_valid(&self) -> bool {
-        self.1 > self.0
-    }
-
-    fn clamp(&self, limits: (i32, i32)) -> Self {
-        Range(cmp::max(self.0, limits.0), cmp::min(self.1, limits.1))
-    }
-
-    fn intersection(&self, r: &Range) -> Option<Range> {
-        let inner_left = cmp::max(self.0, r.0);
-        let inner_right = cmp::min(self.1, r.1);
-        if inner_left >= inner_right {
-            None
-        } else {
-            Some(Range(inner_left, inner_right))
-        }
-    }
-
-    fn len(&self) -> u64 {
-        (self.1 - self.0).abs() as u64
-    }
-}
-
-impl FromStr for Range {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split('=');
-        let mut parts = parts.skip(1).next().unwrap().split("..");
-        let left: i32 = parts.next().unwrap().parse()?;
-        let right: i32 = parts.next().unwrap().parse()?;
-        Ok(Range(left, right + 1))
-    }
-}
+use adventofcode_derive::ParseLine;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Cuboid(Range, Range, Range);
+use crate::{
+    arith,
+    interval::{Interval, IntervalBox},
+    params::{Param, Params},
+};
 
-impl Cuboid {
-    fn is_valid(&self) -> bool {
-        self.0.is_valid() && self.1.is_valid() && self.2.is_valid()
-    }
-
-    fn clamp(&self, limits: (i32, i32)) -> Self {
-        Cuboid(
-            self.0.clamp(limits),
-            self.1.clamp(limits),
-            self.2.clamp(limits),
-        )
-    }
-
-    fn intersection(&self, r: &Cuboid) -> Option<Cuboid> {
-        if let Some(x_ranges) = self.0.intersection(&r.0) {
-            if let Some(y_ranges) = self.1.intersection(&r.1) {
-                if let Some(z_ranges) = self.2.intersection(&r.2) {
-                    return Some(Cuboid(x_ranges, y_ranges, z_ranges));
-                }
-            }
-        }
-        None
-    }
-
-    fn volume(&self) -> u64 {
-        self.0.len() * self.1.len() * self.2.len()
+#[allow(dead_code)]
+pub const URL: &str = "https://adventofcode.com/2021/day/22/input";
+pub const TITLE: &str = "Reactor Reboot";
+pub const VERSION: &str = "1";
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "clamp_start",
+        parts: &[1],
+        default: -50,
+        min: -1_000_000,
+        max: 1_000_000,
+    },
+    Param {
+        name: "clamp_end",
+        parts: &[1],
+        default: 51,
+        min: -1_000_000,
+        max: 1_000_000,
+    },
+];
+
+/// The cubes from start to end (exclusive) on every axis.
+pub type Cuboid = IntervalBox<i32, 3>;
+
+#[derive(Debug, ParseLine)]
+#[pattern("{0}..{1}")]
+struct Span(i32, i32);
+
+// spans include their end, intervals don't
+impl TryFrom<Span> for Interval<i32> {
+    type Error = String;
+
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        let end = span
+            .1
+            .checked_add(1)
+            .ok_or_else(|| format!("{} is too large", span.1))?;
+        Ok(Interval::new(span.0, end))
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Action {
+pub enum Action {
     On,
     Off,
 }
@@ -92,39 +67,46 @@ impl FromStr for Action {
     }
 }
 
-#[derive(Debug)]
-struct Step {
+#[derive(Debug, ParseLine)]
+#[pattern("{action} x={x},y={y},z={z}")]
+struct StepLine {
     action: Action,
-    cuboid: Cuboid,
+    x: Span,
+    y: Span,
+    z: Span,
+}
+
+/// A reboot step, parsed from a line like `on x=10..12,y=10..12,z=10..12`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub action: Action,
+    pub cuboid: Cuboid,
 }
 
 impl FromStr for Step {
-    type Err = &'static str;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
-        let action = parts.next().unwrap().parse().unwrap();
-        let mut ranges = parts.next().unwrap().split(',').map(|s| s.parse().unwrap());
-
-        let cuboid = Cuboid(
-            ranges.next().unwrap(),
-            ranges.next().unwrap(),
-            ranges.next().unwrap(),
-        );
+        let StepLine { action, x, y, z } = s.parse()?;
+        let cuboid = IntervalBox([x.try_into()?, y.try_into()?, z.try_into()?]);
         Ok(Step { action, cuboid })
     }
 }
 
-fn get_inputs(text: &str) -> Vec<Step> {
+pub fn get_inputs(text: &str) -> Vec<Step> {
     text.lines()
         .map(|line| line.trim().parse().unwrap())
         .collect()
 }
 
-fn volume_after_steps(steps: Vec<Step>) -> u64 {
+/// The number of cubes left on once all the steps are done, in order.
+pub fn volume_after_steps(steps: Vec<Step>) -> u64 {
     let mut visited: Vec<Step> = Vec::new();
-    let mut total_vol = 0;
+    let mut total_vol: u64 = 0;
     for s in steps {
+        // the overlaps of a step can take away more than they give back before
+        // its own volume is added, so they are summed up separately
+        let (mut added, mut removed): (u64, u64) = (0, 0);
         let mut intersections = Vec::new();
         for visited_step in visited.iter() {
             match s.cuboid.intersection(&visited_step.cuboid) {
@@ -132,14 +114,14 @@ fn volume_after_steps(steps: Vec<Step>) -> u64 {
                 Some(intersection) => {
                     match visited_step.action {
                         Action::Off => {
-                            total_vol += intersection.volume();
+                            added = arith::add(added, intersection.volume());
                             intersections.push(Step {
                                 action: Action::On,
                                 cuboid: intersection,
                             });
                         }
                         Action::On => {
-                            total_vol -= intersection.volume();
+                            removed = arith::add(removed, intersection.volume());
                             intersections.push(Step {
                                 action: Action::Off,
                                 cuboid: intersection,
@@ -150,8 +132,9 @@ fn volume_after_steps(steps: Vec<Step>) -> u64 {
             }
         }
         visited.extend(intersections);
+        total_vol = arith::sub(arith::add(total_vol, added), removed);
         if let Action::On = s.action {
-            total_vol += s.cuboid.volume();
+            total_vol = arith::add(total_vol, s.cuboid.volume());
             visited.push(s);
         }
     }
@@ -160,18 +143,27 @@ fn volume_after_steps(steps: Vec<Step>) -> u64 {
 
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> u64 {
+    solve1_with(text, &Params::new())
+}
+
+pub fn solve1_with(text: &str, params: &Params) -> u64 {
+    let region = Interval::new(
+        params.get(PARAMS, "clamp_start") as i32,
+        params.get(PARAMS, "clamp_end") as i32,
+    );
     let steps: Vec<Step> = get_inputs(text)
         .iter()
         .map(|s| Step {
             action: s.action,
-            cuboid: s.cuboid.clamp((-50, 51)),
+            cuboid: s.cuboid.clamp(&region),
         })
-        .filter(|s| s.cuboid.is_valid())
+        .filter(|s| !s.cuboid.is_empty())
         .collect();
 
     volume_after_steps(steps)
 }
 
+// the whole space counts here, nothing to clamp
 #[allow(dead_code)]
 pub fn solve2(text: &str) -> u64 {
     let steps = get_inputs(text);
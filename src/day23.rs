@@ -1,28 +1,28 @@
-use std::cmp;
+use adventofcode_derive::CharEnum;
+
+use crate::{export::Rgb, grid::Grid, progress, search, sim::Simulation};
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/23/input";
+pub const TITLE: &str = "Amphipod";
+pub const VERSION: &str = "1";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum AType {
+/// The amphipod kinds, written as A, B, C and D in the burrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CharEnum)]
+pub enum AType {
+    #[ch('A')]
     Amber,
+    #[ch('B')]
     Bronze,
+    #[ch('C')]
     Copper,
+    #[ch('D')]
     Desert,
 }
 
 impl AType {
-    fn from_char(c: char) -> Self {
-        match c {
-            'A' => AType::Amber,
-            'B' => AType::Bronze,
-            'C' => AType::Copper,
-            'D' => AType::Desert,
-            _ => panic!("bad type of an amphipod"),
-        }
-    }
-
-    fn room_no(&self) -> usize {
+    /// The side room the kind belongs in, from the left.
+    pub fn room_no(&self) -> usize {
         match self {
             AType::Amber => 0,
             AType::Bronze => 1,
@@ -35,7 +35,7 @@ impl AType {
         (self.room_no() + 1) * 2
     }
 
-    fn step_energy(&self) -> u64 {
+    pub fn step_energy(&self) -> u64 {
         match self {
             AType::Amber => 1,
             AType::Bronze => 10,
@@ -45,23 +45,32 @@ impl AType {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Amphipod {
-    typ: AType,
-    is_settled: bool,
+/// An amphipod, settled once it's in its own room with nobody of another kind below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Amphipod {
+    pub typ: AType,
+    pub is_settled: bool,
 }
 
 fn is_entrance(idx: usize) -> bool {
     idx == 2 || idx == 4 || idx == 6 || idx == 8
 }
 
-#[derive(Debug)]
-struct Hallway {
-    cells: [Option<Amphipod>; 11],
+/// The hallway cells from left to right, the ones above the rooms are never stopped at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hallway {
+    pub cells: [Option<Amphipod>; 11],
+}
+
+impl Default for Hallway {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Hallway {
-    fn new() -> Hallway {
+    /// An empty hallway.
+    pub fn new() -> Hallway {
         Hallway { cells: [None; 11] }
     }
 
@@ -137,14 +146,16 @@ impl Hallway {
     }
 }
 
-#[derive(Debug)]
-struct Siderooms {
+/// The four side rooms, each from its entrance down.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Siderooms {
     rooms: [Vec<Option<Amphipod>>; 4],
     room_len: usize,
 }
 
 impl Siderooms {
-    fn new(amphipods: Vec<Vec<Amphipod>>) -> Siderooms {
+    /// Full rooms with the amphipods of each, as given by `get_inputs`.
+    pub fn new(amphipods: Vec<Vec<Amphipod>>) -> Siderooms {
         let room_len = amphipods.first().unwrap().len();
         let mut rooms = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
         for (i, r) in amphipods.into_iter().enumerate() {
@@ -155,6 +166,14 @@ impl Siderooms {
         Siderooms { rooms, room_len }
     }
 
+    pub fn rooms(&self) -> &[Vec<Option<Amphipod>>; 4] {
+        &self.rooms
+    }
+
+    pub fn room_len(&self) -> usize {
+        self.room_len
+    }
+
     fn settle_idx(&self, a_type: AType) -> Option<usize> {
         let room = &self.rooms[a_type.room_no()];
         let mut cells = room.iter();
@@ -216,7 +235,8 @@ impl Siderooms {
         }
     }
 
-    fn is_done(&self, i: usize) -> bool {
+    /// Whether room i is full of its own kind.
+    pub fn is_done(&self, i: usize) -> bool {
         for cell in &self.rooms[i] {
             match cell {
                 None => return false,
@@ -230,7 +250,7 @@ impl Siderooms {
         true
     }
 
-    fn are_all_done(&self) -> bool {
+    pub fn are_all_done(&self) -> bool {
         for i in 0..self.rooms.len() {
             if !self.is_done(i) {
                 return false;
@@ -263,12 +283,9 @@ impl Siderooms {
     }
 }
 
-fn get_min_energy(hallway: Hallway, siderooms: Siderooms, energy: u64, total_min: u64) -> u64 {
-    if siderooms.are_all_done() {
-        return energy;
-    }
-
-    let mut min_energy = u64::MAX;
+/// Every state one amphipod move away, with the energy the move takes.
+pub fn next_states(hallway: &Hallway, siderooms: &Siderooms) -> Vec<((Hallway, Siderooms), u64)> {
+    let mut states = Vec::new();
 
     for hallway_idx in hallway.iter_occupied() {
         let a_type = hallway.cells[hallway_idx].unwrap().typ;
@@ -285,27 +302,14 @@ fn get_min_energy(hallway: Hallway, siderooms: Siderooms, energy: u64, total_min
 
         let (new_hallway, mut amphipod) = hallway.drop(hallway_idx);
 
-        let hw_steps = (hallway_idx as i32 - room_entrance as i32).abs() as u64;
+        let hw_steps = (hallway_idx as i32 - room_entrance as i32).unsigned_abs() as u64;
         let r_steps = room_idx as u64 + 1;
         let energy_delta = (hw_steps + r_steps) * amphipod.typ.step_energy();
-        let new_energy = energy + energy_delta;
-        if new_energy > total_min {
-            continue;
-        }
 
         amphipod.is_settled = true;
 
         let new_siderooms = siderooms.put(amphipod, room_idx);
-
-        min_energy = cmp::min(
-            min_energy,
-            get_min_energy(
-                new_hallway,
-                new_siderooms,
-                new_energy,
-                cmp::min(total_min, min_energy),
-            ),
-        );
+        states.push(((new_hallway, new_siderooms), energy_delta));
     }
 
     for (room_no, room_idx) in siderooms.iter_ready() {
@@ -313,32 +317,137 @@ fn get_min_energy(hallway: Hallway, siderooms: Siderooms, energy: u64, total_min
         for hallway_idx in hallway.paths_from(room_entrance) {
             let (new_siderooms, amphipod) = siderooms.drop(room_no, room_idx);
 
-            let hw_steps = (hallway_idx as i32 - room_entrance as i32).abs() as u64;
+            let hw_steps = (hallway_idx as i32 - room_entrance as i32).unsigned_abs() as u64;
             let r_steps = room_idx as u64 + 1;
             let energy_delta = (hw_steps + r_steps) * amphipod.typ.step_energy();
-            let new_energy = energy + energy_delta;
-            if new_energy > total_min {
-                continue;
-            }
 
             let new_hallway = hallway.put(amphipod, hallway_idx);
+            states.push(((new_hallway, new_siderooms), energy_delta));
+        }
+    }
+
+    states
+}
+
+/// The cheapest way to get everybody home, every state on the way included.
+pub fn min_energy_path(hallway: Hallway, siderooms: Siderooms) -> (Vec<(Hallway, Siderooms)>, u64) {
+    let mut expanded = 0;
+    search::dijkstra(
+        &(hallway, siderooms),
+        |(hallway, siderooms)| {
+            expanded += 1;
+            progress::report("states expanded", expanded, None);
+            next_states(hallway, siderooms)
+        },
+        |(_, siderooms)| siderooms.are_all_done(),
+    )
+    .unwrap()
+}
+
+pub fn get_min_energy(hallway: Hallway, siderooms: Siderooms) -> u64 {
+    let (_, energy) = min_energy_path(hallway, siderooms);
+    energy
+}
 
-            min_energy = cmp::min(
-                min_energy,
-                get_min_energy(
-                    new_hallway,
-                    new_siderooms,
-                    new_energy,
-                    cmp::min(total_min, min_energy),
-                ),
-            );
+fn cell_char(cell: &Option<Amphipod>) -> char {
+    cell.map_or('.', |a| char::from(&a.typ))
+}
+
+/// The burrow drawn the way the puzzle does.
+pub fn burrow_to_str(hallway: &Hallway, siderooms: &Siderooms) -> String {
+    let mut res = String::from("#############\n#");
+    res.extend(hallway.cells.iter().map(cell_char));
+    res.push_str("#\n");
+    for depth in 0..siderooms.room_len {
+        res.push_str(if depth == 0 { "###" } else { "  #" });
+        for room in siderooms.rooms.iter() {
+            res.push(cell_char(&room[depth]));
+            res.push('#');
         }
+        res.push_str(if depth == 0 { "##\n" } else { "\n" });
     }
+    res.push_str("  #########\n");
+    res
+}
 
-    min_energy
+#[derive(Clone)]
+struct Moves {
+    path: Vec<(Hallway, Siderooms)>,
+    // energy spent to get to each state of the path
+    energies: Vec<u64>,
+    curr: usize,
+}
+
+impl Simulation for Moves {
+    fn step(&mut self) -> bool {
+        if self.curr + 1 >= self.path.len() {
+            return false;
+        }
+        self.curr += 1;
+        true
+    }
+
+    fn frame(&self) -> Grid<Rgb> {
+        let burrow = self.render();
+        let lines: Vec<&str> = burrow.lines().collect();
+        let width = lines.iter().map(|l| l.len()).max().unwrap();
+        let cells = lines
+            .iter()
+            .flat_map(|l| l.chars().chain(std::iter::repeat(' ')).take(width))
+            .map(|c| match c {
+                '#' => [0x66, 0x66, 0x66],
+                'A' => [0xff, 0xbf, 0x00],
+                'B' => [0xcd, 0x7f, 0x32],
+                'C' => [0xb8, 0x73, 0x33],
+                'D' => [0xed, 0xc9, 0xaf],
+                _ => [0x0f, 0x0f, 0x23],
+            })
+            .collect();
+        Grid::new(lines.len(), width, cells)
+    }
+
+    fn render(&self) -> String {
+        let (hallway, siderooms) = &self.path[self.curr];
+        burrow_to_str(hallway, siderooms)
+    }
+
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("move", self.curr as u64),
+            ("total moves", self.path.len() as u64 - 1),
+            ("energy", self.energies[self.curr]),
+            ("total energy", *self.energies.last().unwrap()),
+        ]
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+pub fn simulation(text: &str) -> impl Simulation {
+    let amphipods = get_inputs(text.lines());
+    let (path, _) = min_energy_path(Hallway::new(), Siderooms::new(amphipods));
+
+    let mut energies = vec![0];
+    for states in path.windows(2) {
+        let (hallway, siderooms) = &states[0];
+        let (_, energy) = next_states(hallway, siderooms)
+            .into_iter()
+            .find(|(s, _)| *s == states[1])
+            .unwrap();
+        energies.push(energies.last().unwrap() + energy);
+    }
+
+    Moves {
+        path,
+        energies,
+        curr: 0,
+    }
 }
 
-fn get_inputs<'a>(lines: impl Iterator<Item = &'a str> + 'a) -> Vec<Vec<Amphipod>> {
+/// The amphipods of each room from the burrow drawing, from the entrance down.
+pub fn get_inputs<'a>(lines: impl Iterator<Item = &'a str> + 'a) -> Vec<Vec<Amphipod>> {
     let mut amphipods = vec![vec![]; 4];
     for line in lines.skip(2) {
         let mut room_idx = 0;
@@ -347,7 +456,7 @@ fn get_inputs<'a>(lines: impl Iterator<Item = &'a str> + 'a) -> Vec<Vec<Amphipod
                 continue;
             }
             amphipods[room_idx].push(Amphipod {
-                typ: AType::from_char(c),
+                typ: AType::try_from(c).unwrap(),
                 is_settled: false,
             });
             room_idx += 1;
@@ -370,7 +479,7 @@ pub fn solve1(text: &str) -> u64 {
     let amphipods = get_inputs(text.lines());
     let hallway = Hallway::new();
     let siderooms = Siderooms::new(amphipods);
-    get_min_energy(hallway, siderooms, 0, u64::MAX)
+    get_min_energy(hallway, siderooms)
 }
 
 #[allow(dead_code)]
@@ -384,5 +493,5 @@ pub fn solve2(text: &str) -> u64 {
     );
     let hallway = Hallway::new();
     let siderooms = Siderooms::new(amphipods);
-    get_min_energy(hallway, siderooms, 0, u64::MAX)
+    get_min_energy(hallway, siderooms)
 }
@@ -1,12 +1,17 @@
 // Reverse-engineered algorithm by hand
 
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
+
+use crate::progress;
 
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/24/input";
+pub const TITLE: &str = "Arithmetic Logic Unit";
+pub const VERSION: &str = "1";
 
+/// An instruction argument, a variable out of w, x, y and z or a number.
 #[derive(Debug, Clone)]
-enum Arg {
+pub enum Arg {
     Var(String),
     Val(i64),
 }
@@ -22,8 +27,9 @@ impl FromStr for Arg {
     }
 }
 
+/// An ALU instruction, parsed from a line of the program.
 #[derive(Debug)]
-enum Op {
+pub enum Op {
     Inp(Arg),
     Add(Arg, Arg),
     Mul(Arg, Arg),
@@ -76,7 +82,8 @@ impl FromStr for Op {
     }
 }
 
-fn get_inputs(text: &str) -> Vec<Vec<Op>> {
+/// The program split in a block per digit, without the `inp w` starting each.
+pub fn get_inputs(text: &str) -> Vec<Vec<Op>> {
     text.split("inp w")
         .filter(|s| !s.trim().is_empty())
         .map(|s| {
@@ -165,222 +172,111 @@ pub fn solve2(text: &str) -> u64 {
     String::from_iter(chars.into_iter()).parse().unwrap()
 }
 
-// Bruteforce
-
-// use std::{collections::HashMap, str::FromStr, ops::RangeInclusive, time::Instant};
-
-// #[allow(dead_code)]
-// pub const URL: &str = "https://adventofcode.com/2021/day/24/input";
-
-// #[derive(Debug)]
-// struct State {
-//     vars: HashMap<String, i64>,
-//     input: [i64; 14],
-//     curr_input_idx: usize,
-// }
-
-// impl State {
-//     fn new(input: [i64; 14]) -> Self {
-//         let mut vars = HashMap::new();
-//         vars.insert("w".to_string(), 0);
-//         vars.insert("x".to_string(), 0);
-//         vars.insert("y".to_string(), 0);
-//         vars.insert("z".to_string(), 0);
-
-//         State {
-//             vars,
-//             input,
-//             curr_input_idx: 0,
-//         }
-//     }
-
-//     fn input(&mut self, var: String) {
-//         if self.curr_input_idx >= self.input.len() {
-//             panic!("trying to read out of bounds")
-//         }
-//         self.vars.insert(var, self.input[self.curr_input_idx]);
-//         self.curr_input_idx += 1;
-//     }
-
-//     fn get(&self, var: String) -> &i64 {
-//         self.vars.get(&var).unwrap_or(&0)
-//     }
-
-//     fn apply(&mut self, var1: String, var2: String, func: fn(i64, i64) -> i64) {
-//         let n2 = *self.get(var2);
-//         let n1 = self.vars.entry(var1).or_default();
-//         *n1 = func(*n1, n2);
-//     }
-
-//     fn apply_val(&mut self, var: String, val: i64, func: fn(i64, i64) -> i64) {
-//         let n1 = self.vars.entry(var).or_default();
-//         *n1 = func(*n1, val);
-//     }
-// }
+// Search through the ALU itself
 
-// #[derive(Debug)]
-// enum Arg {
-//     Var(String),
-//     Val(i64),
-// }
-
-// impl FromStr for Arg {
-//     type Err = &'static str;
-
-//     fn from_str(s: &str) -> Result<Self, Self::Err> {
-//         return match s.parse::<i64>() {
-//             Ok(v) => Ok(Arg::Val(v)),
-//             _ => Ok(Arg::Var(String::from(s))),
-//         };
-//     }
-// }
-
-// #[derive(Debug)]
-// enum Op {
-//     Inp(Arg),
-//     Add(Arg, Arg),
-//     Mul(Arg, Arg),
-//     Div(Arg, Arg),
-//     Mod(Arg, Arg),
-//     Eql(Arg, Arg),
-// }
-
-// impl Op {
-//     fn func(&self) -> fn(i64, i64) -> i64 {
-//         match self {
-//             Op::Inp(_) => panic!("no func associated with <inp> instruction"),
-//             Op::Add(_, _) => |n1, n2| n1 + n2,
-//             Op::Mul(_, _) => |n1, n2| n1 * n2,
-//             Op::Div(_, _) => |n1, n2| {
-//                 if n2 == 0 {
-//                     panic!("cant divide by 0");
-//                 }
-//                 n1 / n2
-//             },
-//             Op::Mod(_, _) => |n1, n2| {
-//                 if n1 < 0 || n2 <= 0 {
-//                     panic!("cant mod with nagative numbers");
-//                 }
-//                 n1 % n2
-//             },
-//             Op::Eql(_, _) => |n1, n2| if n1 == n2 { 1 } else { 0 },
-//         }
-//     }
-
-//     fn eval(&self, s: &mut State) {
-//         match self {
-//             Op::Inp(arg) => {
-//                 match arg {
-//                     Arg::Val(_) => panic!("cant write into value"),
-//                     Arg::Var(v) => { s.input(v.to_string()); },
-//                 };
-//             }
-//             op @ (
-//                 Op::Add(arg1, arg2)
-//                 | Op::Mul(arg1, arg2)
-//                 | Op::Div(arg1, arg2)
-//                 | Op::Mod(arg1, arg2)
-//                 | Op::Eql(arg1, arg2)
-//             ) => {
-//                 match arg1 {
-//                     Arg::Val(_) => panic!("cant write into value"),
-//                     Arg::Var(v1) => match arg2 {
-//                         Arg::Val(n2) => s.apply_val(v1.to_string(), *n2, op.func()),
-//                         Arg::Var(v2) => s.apply(v1.to_string(), v2.to_string(), op.func()),
-//                     }
-//                 };
-//             }
-//         }
-//     }
-// }
+fn var_index(var: &str) -> usize {
+    match var {
+        "w" => 0,
+        "x" => 1,
+        "y" => 2,
+        "z" => 3,
+        _ => panic!("unknown variable {}", var),
+    }
+}
 
-// impl FromStr for Op {
-//     type Err = &'static str;
+impl Op {
+    fn eval(&self, vars: &mut [i64; 4]) {
+        let (var, arg) = match self {
+            Op::Inp(_) => panic!("inputs are read between blocks"),
+            Op::Add(Arg::Var(var), arg)
+            | Op::Mul(Arg::Var(var), arg)
+            | Op::Div(Arg::Var(var), arg)
+            | Op::Mod(Arg::Var(var), arg)
+            | Op::Eql(Arg::Var(var), arg) => (var_index(var), arg),
+            _ => unreachable!(),
+        };
+        let n2 = match arg {
+            Arg::Val(v) => *v,
+            Arg::Var(v) => vars[var_index(v)],
+        };
+        let n1 = vars[var];
+        vars[var] = match self {
+            Op::Add(_, _) => n1 + n2,
+            Op::Mul(_, _) => n1 * n2,
+            Op::Div(_, _) => {
+                assert!(n2 != 0, "cant divide by 0");
+                n1 / n2
+            }
+            Op::Mod(_, _) => {
+                assert!(n1 >= 0 && n2 > 0, "cant mod with negative numbers");
+                n1 % n2
+            }
+            Op::Eql(_, _) => (n1 == n2) as i64,
+            Op::Inp(_) => unreachable!(),
+        };
+    }
+}
 
-//     fn from_str(s: &str) -> Result<Self, Self::Err> {
-//         let mut parts = s.split_whitespace();
-//         match parts.next().ok_or("empty instruction")? {
-//             "inp" => {
-//                 let arg = parts.next().ok_or("no args for inp")?;
-//                 Ok(Op::Inp(Arg::Var(String::from(arg))))
-//             },
-//             op @ ("add" | "mul" | "div" | "mod" | "eql") => {
-//                 let arg1 = parts.next().ok_or("no args")?.parse()?;
-//                 let arg2 = parts.next().ok_or("no args")?.parse()?;
-//                 if let Arg::Val(_) = arg1 {
-//                     return Err("can't write into value");
-//                 }
+/// z once the block has run after reading the digit into w.
+pub fn run_block(block: &[Op], z: i64, digit: i64) -> i64 {
+    let mut vars = [digit, 0, 0, z];
+    block.iter().for_each(|op| op.eval(&mut vars));
+    vars[3]
+}
 
-//                 match op {
-//                     "add" => Ok(Op::Add(arg1, arg2)),
-//                     "mul" => Ok(Op::Mul(arg1, arg2)),
-//                     "div" => Ok(Op::Div(arg1, arg2)),
-//                     "mod" => Ok(Op::Mod(arg1, arg2)),
-//                     "eql" => Ok(Op::Eql(arg1, arg2)),
-//                     _ => unreachable!(),
-//                 }
-//             }
-//             o => {
-//                 println!("{}", o);
-//                 Err("unknown instruction")
-//             },
-//         }
-//     }
-// }
+// a block can only shrink z by dividing it, so a z as big as all the divisions
+// left can't get back to 0
+fn z_limits(stages: &[Vec<Op>]) -> Vec<i64> {
+    let mut limits = vec![1i64; stages.len() + 1];
+    for (i, block) in stages.iter().enumerate().rev() {
+        let div = block
+            .iter()
+            .filter_map(|op| match op {
+                Op::Div(Arg::Var(v), Arg::Val(n)) if v == "z" => Some(*n),
+                _ => None,
+            })
+            .product::<i64>();
+        limits[i] = limits[i + 1].saturating_mul(div);
+    }
+    limits
+}
 
-// fn get_inputs(text: &str) -> Vec<Op> {
-//     text.lines().map(|l| l.parse().unwrap()).collect()
-// }
+fn search(
+    stages: &[Vec<Op>],
+    limits: &[i64],
+    digits: &[i64; 9],
+    i: usize,
+    z: i64,
+    dead_ends: &mut HashSet<(usize, i64)>,
+) -> Option<u64> {
+    if i == stages.len() {
+        return (z == 0).then_some(0);
+    }
+    if z >= limits[i] || dead_ends.contains(&(i, z)) {
+        return None;
+    }
+    for &digit in digits {
+        let next = run_block(&stages[i], z, digit);
+        if let Some(rest) = search(stages, limits, digits, i + 1, next, dead_ends) {
+            let shift = 10u64.pow((stages.len() - i - 1) as u32);
+            return Some(digit as u64 * shift + rest);
+        }
+    }
+    dead_ends.insert((i, z));
+    progress::report("dead ends", dead_ends.len() as u64, None);
+    None
+}
 
-// fn next_input_num(
-//     digit_gens: &mut Vec<RangeInclusive<i64>>,
-//     mut num: [i64; 14],
-// ) -> Option<[i64; 14]> {
-//     for i in 0..14 {
-//         let cur_iter = &mut digit_gens[i];
-//         match cur_iter.next() {
-//             None => {
-//                 if i == 13 {
-//                     return None
-//                 }
-//                 digit_gens[i] = 2..=9;
-//                 num[i] = 1;
-//                 continue;
-//             }
-//             Some(v) => {
-//                 num[i] = v;
-//                 break;
-//             }
-//         }
-//     }
-//     Some(num)
-// }
+fn solve_search(text: &str, digits: [i64; 9]) -> u64 {
+    let stages = get_inputs(text);
+    let limits = z_limits(&stages);
+    search(&stages, &limits, &digits, 0, 0, &mut HashSet::new()).expect("no valid model number")
+}
 
-// #[allow(dead_code)]
-// pub fn solve1(text: &str) -> u64 {
-//     let ops = get_inputs(text);
-//     let mut digit_gens: Vec<RangeInclusive<i64>> = (0..13).map(|_| (2..=9)).collect();
-//     digit_gens.insert(0, 1..=9);
-//     let mut prev_num = [1; 14];
-//     prev_num[0] = 0;
+pub fn solve1_search(text: &str) -> u64 {
+    solve_search(text, [9, 8, 7, 6, 5, 4, 3, 2, 1])
+}
 
-//     let mut c = 0 as u64;
-//     let mut good_nums = Vec::new();
-//     let now = Instant::now();
-//     while let Some(num) = next_input_num(&mut digit_gens, prev_num) {
-//         if c % 100000 == 0 {
-//             println!("{} {}", c, now.elapsed().as_secs());
-//         }
-//         let mut state = State::new(num);
-//         ops.iter().for_each(|op| {
-//             op.eval(&mut state);
-//         });
-//         if *state.get("z".to_string()) == 0 {
-//             good_nums.push(num);
-//             println!("{:?}", num)
-//         }
-//         prev_num = num;
-//         c += 1;
-//     }
-//     0
-// }
+pub fn solve2_search(text: &str) -> u64 {
+    solve_search(text, [1, 2, 3, 4, 5, 6, 7, 8, 9])
+}
@@ -1,55 +1,64 @@
+use adventofcode_derive::CharEnum;
+
+use crate::{
+    export::{self, Palette, Rgb},
+    grid::Grid,
+    sim::Simulation,
+};
+
 #[allow(dead_code)]
 pub const URL: &str = "https://adventofcode.com/2021/day/25/input";
+pub const TITLE: &str = "Sea Cucumber";
+pub const VERSION: &str = "1";
 
-#[derive(Debug, Clone)]
-enum SeaCucumber {
+/// A spot on the sea floor, empty or taken by a cucumber of either herd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CharEnum)]
+pub enum Cell {
+    #[ch('>')]
     Right,
+    #[ch('v')]
     Down,
+    #[ch('.')]
+    Empty,
 }
 
-impl SeaCucumber {
-    fn from_char(c: char) -> Option<Self> {
-        match c {
-            '>' => Some(Self::Right),
-            'v' => Some(Self::Down),
-            '.' => None,
-            _ => panic!("unexpected char"),
+impl Palette for Cell {
+    fn rgb(&self) -> Rgb {
+        match self {
+            Cell::Right => [0xff, 0x99, 0x33],
+            Cell::Down => [0x33, 0xcc, 0xbb],
+            Cell::Empty => export::BLACK,
         }
     }
 }
 
-fn get_inputs(text: &str) -> Vec<Vec<Option<SeaCucumber>>> {
-    text.lines()
-        .map(|l| l.chars().map(SeaCucumber::from_char).collect())
-        .collect()
+pub fn get_inputs(text: &str) -> Grid<Cell> {
+    text.parse().unwrap()
 }
 
-fn proceed(floor: &Vec<Vec<Option<SeaCucumber>>>) -> (bool, Vec<Vec<Option<SeaCucumber>>>) {
-    let height = floor.len();
-    let width = floor.first().unwrap().len();
+/// One step, the east-facing herd moves first, with how many cucumbers moved.
+pub fn proceed(floor: &Grid<Cell>) -> (usize, Grid<Cell>) {
+    let height = floor.height();
+    let width = floor.width();
 
     let mut new_floor = floor.clone();
-    let mut moved = false;
+    let mut moved = 0;
     for i in 0..height {
         for j in 0..width {
-            if let Some(SeaCucumber::Right) = floor[i][j] {
-                if let None = floor[i][(j + 1) % width] {
-                    moved = true;
-                    new_floor[i][j] = None;
-                    new_floor[i][(j + 1) % width] = Some(SeaCucumber::Right);
-                }
+            if floor[(i, j)] == Cell::Right && floor[(i, (j + 1) % width)] == Cell::Empty {
+                moved += 1;
+                new_floor[(i, j)] = Cell::Empty;
+                new_floor[(i, (j + 1) % width)] = Cell::Right;
             }
         }
     }
     let floor = new_floor.clone();
     for i in 0..height {
         for j in 0..width {
-            if let Some(SeaCucumber::Down) = floor[i][j] {
-                if let None = floor[(i + 1) % height][j] {
-                    moved = true;
-                    new_floor[i][j] = None;
-                    new_floor[(i + 1) % height][j] = Some(SeaCucumber::Down);
-                }
+            if floor[(i, j)] == Cell::Down && floor[((i + 1) % height, j)] == Cell::Empty {
+                moved += 1;
+                new_floor[(i, j)] = Cell::Empty;
+                new_floor[((i + 1) % height, j)] = Cell::Down;
             }
         }
     }
@@ -57,6 +66,44 @@ fn proceed(floor: &Vec<Vec<Option<SeaCucumber>>>) -> (bool, Vec<Vec<Option<SeaCu
     (moved, new_floor)
 }
 
+#[derive(Clone)]
+struct Herds {
+    floor: Grid<Cell>,
+    moved: usize,
+}
+
+impl Simulation for Herds {
+    fn step(&mut self) -> bool {
+        let (moved, floor) = proceed(&self.floor);
+        self.floor = floor;
+        self.moved = moved;
+        moved > 0
+    }
+
+    fn frame(&self) -> Grid<Rgb> {
+        export::to_frame(&self.floor)
+    }
+
+    fn render(&self) -> String {
+        self.floor.to_string()
+    }
+
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        vec![("moved cucumbers", self.moved as u64)]
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Simulation> {
+        Box::new(self.clone())
+    }
+}
+
+pub fn simulation(text: &str) -> impl Simulation {
+    Herds {
+        floor: get_inputs(text),
+        moved: 0,
+    }
+}
+
 #[allow(dead_code)]
 pub fn solve1(text: &str) -> u64 {
     let mut floor = get_inputs(text);
@@ -65,7 +112,7 @@ pub fn solve1(text: &str) -> u64 {
     let mut moved = true;
     while moved {
         let (new_moved, new_floor) = proceed(&floor);
-        moved = new_moved;
+        moved = new_moved > 0;
         floor = new_floor;
         count += 1;
     }
@@ -0,0 +1,166 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0x0f, 0x0f, 0x23];
+pub const WHITE: Rgb = [0xff, 0xff, 0xff];
+
+pub trait Palette {
+    fn rgb(&self) -> Rgb;
+}
+
+impl Palette for bool {
+    fn rgb(&self) -> Rgb {
+        if *self {
+            WHITE
+        } else {
+            BLACK
+        }
+    }
+}
+
+impl Palette for Rgb {
+    fn rgb(&self) -> Rgb {
+        *self
+    }
+}
+
+pub fn to_frame<T: Palette>(grid: &Grid<T>) -> Grid<Rgb> {
+    grid.map(Palette::rgb)
+}
+
+// every cell becomes a `scale` x `scale` square
+fn to_pixels(frame: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let mut pixels = Vec::with_capacity(frame.height() * frame.width() * scale * scale * 3);
+    for row in frame.rows() {
+        for _ in 0..scale {
+            for rgb in row {
+                for _ in 0..scale {
+                    pixels.extend_from_slice(rgb);
+                }
+            }
+        }
+    }
+    pixels
+}
+
+pub fn write_png<T: Palette>(
+    path: impl AsRef<Path>,
+    grid: &Grid<T>,
+    scale: usize,
+) -> io::Result<()> {
+    encode_png(BufWriter::new(File::create(path)?), grid, scale)
+}
+
+pub fn png_bytes<T: Palette>(grid: &Grid<T>, scale: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    encode_png(&mut bytes, grid, scale)?;
+    Ok(bytes)
+}
+
+fn encode_png<T: Palette>(out: impl Write, grid: &Grid<T>, scale: usize) -> io::Result<()> {
+    let frame = to_frame(grid);
+    let mut encoder = png::Encoder::new(
+        out,
+        dimension(frame.width(), scale)?,
+        dimension(frame.height(), scale)?,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&to_pixels(&frame, scale))?;
+    Ok(())
+}
+
+// frames are kept in memory until written, so the grid is allowed to change its size
+// between steps, smaller frames get centered and padded with their corner color
+pub struct GifRecorder {
+    frames: Vec<Grid<Rgb>>,
+    scale: usize,
+    delay: u16,
+}
+
+impl GifRecorder {
+    pub fn new(scale: usize, delay_ms: u16) -> Self {
+        GifRecorder {
+            frames: Vec::new(),
+            scale,
+            delay: delay_ms / 10,
+        }
+    }
+
+    pub fn record<T: Palette>(&mut self, grid: &Grid<T>) {
+        self.frames.push(to_frame(grid));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let height = self.frames.iter().map(Grid::height).max().unwrap_or(0);
+        let width = self.frames.iter().map(Grid::width).max().unwrap_or(0);
+
+        let (gif_width, gif_height) = (
+            dimension(width, self.scale)?,
+            dimension(height, self.scale)?,
+        );
+        let file = BufWriter::new(File::create(path)?);
+
+        let mut encoder =
+            gif::Encoder::new(file, gif_width, gif_height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for frame in self.frames.iter() {
+            let padded = pad(frame, height, width);
+            let mut gif_frame = gif::Frame::from_rgb_speed(
+                gif_width,
+                gif_height,
+                &to_pixels(&padded, self.scale),
+                10,
+            );
+            gif_frame.delay = self.delay;
+            encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+// the encoders take u16 (gif) or u32 (png) sizes, a bigger image is an error
+fn dimension<T: TryFrom<usize>>(size: usize, scale: usize) -> io::Result<T> {
+    size.checked_mul(scale)
+        .and_then(|pixels| T::try_from(pixels).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "{} cells at scale {} is too large for the image format",
+                    size, scale
+                ),
+            )
+        })
+}
+
+fn pad(frame: &Grid<Rgb>, height: usize, width: usize) -> Grid<Rgb> {
+    if frame.height() == height && frame.width() == width {
+        return frame.clone();
+    }
+    let background = frame.get((0, 0)).copied().unwrap_or(BLACK);
+    let mut padded = Grid::filled(height, width, background);
+    let (top, left) = ((height - frame.height()) / 2, (width - frame.width()) / 2);
+    for ((i, j), rgb) in frame.iter() {
+        padded[(top + i, left + j)] = *rgb;
+    }
+    padded
+}
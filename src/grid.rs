@@ -0,0 +1,168 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+pub type Point = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), height * width, "grid size doesn't match");
+        Grid {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    pub fn filled(height: usize, width: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(height, width, vec![value; height * width])
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, (i, j): Point) -> Option<&T> {
+        if i < self.height && j < self.width {
+            Some(&self.cells[i * self.width + j])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (i, j): Point) -> Option<&mut T> {
+        if i < self.height && j < self.width {
+            Some(&mut self.cells[i * self.width + j])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(idx, c)| ((idx / width, idx % width), c))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.height, self.width, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): Point) -> &Self::Output {
+        assert!(
+            i < self.height && j < self.width,
+            "({}, {}) is off the grid",
+            i,
+            j
+        );
+        &self.cells[i * self.width + j]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, (i, j): Point) -> &mut Self::Output {
+        assert!(
+            i < self.height && j < self.width,
+            "({}, {}) is off the grid",
+            i,
+            j
+        );
+        &mut self.cells[i * self.width + j]
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        let mut height = 0;
+        let mut width = None;
+        // blank lines around the grid are fine, one inside it would merge rows
+        let lines: Vec<&str> = s.lines().map(|l| l.trim_end()).collect();
+        let first = lines
+            .iter()
+            .position(|l| !l.is_empty())
+            .unwrap_or(lines.len());
+        let last = lines
+            .iter()
+            .rposition(|l| !l.is_empty())
+            .map_or(0, |i| i + 1);
+        for (i, line) in lines.iter().enumerate().take(last).skip(first) {
+            if line.is_empty() {
+                return Err(format!("line {} is blank in the middle of the grid", i + 1));
+            }
+            let mut line_width = 0;
+            for (j, c) in line.chars().enumerate() {
+                let cell = T::try_from(c)
+                    .map_err(|e| format!("line {}, column {}: {}", i + 1, j + 1, e))?;
+                cells.push(cell);
+                line_width += 1;
+            }
+            match width {
+                None => width = Some(line_width),
+                Some(w) if w != line_width => {
+                    return Err(format!(
+                        "line {} is {} wide, expected {}",
+                        i + 1,
+                        line_width,
+                        w
+                    ))
+                }
+                _ => (),
+            }
+            height += 1;
+        }
+        Ok(Grid::new(height, width.unwrap_or(0), cells))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for c in row {
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    Empty,
+    // the site sends this instead of the input when the session is missing or expired
+    LoggedOut,
+    NotUnlocked,
+    Html,
+    ServerError(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "the input is empty"),
+            InputError::LoggedOut => write!(
+                f,
+                "got the \"please log in\" page instead of the input, the session is missing or expired"
+            ),
+            InputError::NotUnlocked => write!(f, "the puzzle isn't unlocked yet"),
+            InputError::Html => write!(f, "got an HTML page instead of the input"),
+            InputError::ServerError(line) => write!(f, "got an error instead of the input: {}", line),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl From<InputError> for io::Error {
+    fn from(e: InputError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+fn check(text: &str) -> Result<(), InputError> {
+    let first_line = text.lines().next().unwrap_or("");
+    if text.is_empty() {
+        Err(InputError::Empty)
+    } else if text.contains("Puzzle inputs differ by user") {
+        Err(InputError::LoggedOut)
+    } else if text.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        Err(InputError::NotUnlocked)
+    } else if first_line.starts_with('<') || text.contains("<html") || text.contains("<!DOCTYPE") {
+        Err(InputError::Html)
+    } else if [
+        "404 Not Found",
+        "500 Internal Server Error",
+        "502 Bad Gateway",
+        "503 Service",
+    ]
+    .iter()
+    .any(|e| first_line.contains(e))
+    {
+        Err(InputError::ServerError(String::from(first_line)))
+    } else {
+        Ok(())
+    }
+}
+
+// strips a BOM, turns CRLF into LF, drops trailing whitespace on every line and
+// leaves exactly one newline at the end, so parsers can split on "\n\n" safely
+pub fn sanitize(text: &str) -> Result<String, InputError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut res = String::with_capacity(text.len());
+    for line in text.split('\n') {
+        res.push_str(line.trim_end());
+        res.push('\n');
+    }
+    let res = format!("{}\n", res.trim_end_matches('\n'));
+    check(res.trim())?;
+    Ok(res)
+}
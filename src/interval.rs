@@ -0,0 +1,219 @@
+use std::{cmp, fmt::Display};
+
+use crate::arith;
+
+pub trait Coord: Copy + Ord {
+    fn distance(from: Self, to: Self) -> u64;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                fn distance(from: Self, to: Self) -> u64 {
+                    (to as i128 - from as i128).unsigned_abs() as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+// half-open: start is included, end is not
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Coord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            T::distance(self.start, self.end)
+        }
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.start <= *x && *x < self.end
+    }
+
+    pub fn clamp(&self, limits: &Interval<T>) -> Self {
+        Interval::new(
+            cmp::max(self.start, limits.start),
+            cmp::min(self.end, limits.end),
+        )
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Self> {
+        let inner = self.clamp(other);
+        if inner.is_empty() {
+            None
+        } else {
+            Some(inner)
+        }
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+// sorted, non-empty, non-overlapping and non-adjacent intervals
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Coord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Coord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn normalized(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort_unstable_by_key(|i| i.start);
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for i in intervals {
+            match merged.last_mut() {
+                Some(last) if i.start <= last.end => {
+                    last.end = cmp::max(last.end, i.end);
+                }
+                _ => merged.push(i),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= *x);
+        idx < self.intervals.len() && self.intervals[idx].contains(x)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = IntervalSet::normalized(intervals);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> Self {
+        let intervals = self.iter().chain(other.iter()).copied().collect();
+        IntervalSet::normalized(intervals)
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            if let Some(inner) = a.intersection(b) {
+                intervals.push(inner);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for &a in self.intervals.iter() {
+            let mut start = a.start;
+            while j < other.intervals.len() && other.intervals[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.intervals.len() && other.intervals[k].start < a.end {
+                let b = other.intervals[k];
+                if b.start > start {
+                    intervals.push(Interval::new(start, b.start));
+                }
+                start = cmp::max(start, b.end);
+                k += 1;
+            }
+            if start < a.end {
+                intervals.push(Interval::new(start, a.end));
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Coord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        IntervalSet::normalized(iter.into_iter().collect())
+    }
+}
+
+impl<T: Coord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet::normalized(vec![interval])
+    }
+}
+
+// a box in N dimensions as a product of intervals, one per axis
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct IntervalBox<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: Coord, const N: usize> IntervalBox<T, N> {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    pub fn volume(&self) -> u64 {
+        arith::product(self.0.iter().map(Interval::len))
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.0.iter().zip(point).all(|(i, x)| i.contains(x))
+    }
+
+    pub fn clamp(&self, limits: &Interval<T>) -> Self {
+        IntervalBox(self.0.map(|i| i.clamp(limits)))
+    }
+
+    pub fn intersection(&self, other: &IntervalBox<T, N>) -> Option<Self> {
+        let mut axes = self.0;
+        for (axis, o) in axes.iter_mut().zip(other.0.iter()) {
+            *axis = axis.intersection(o)?;
+        }
+        Some(IntervalBox(axes))
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::answer::Answer;
+
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+    // art has to be checked by hand
+    Unverifiable,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong(expected) => write!(f, "WRONG, expected {}", expected),
+            Verdict::Unknown => write!(f, "unknown"),
+            Verdict::Unverifiable => write!(f, "check by hand"),
+        }
+    }
+}
+
+// one answer per line: "DAY PART ANSWER"
+pub struct KnownAnswers {
+    path: PathBuf,
+    answers: BTreeMap<(u32, u8), String>,
+}
+
+impl KnownAnswers {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: expected DAY PART ANSWER", path.display(), i + 1),
+                )
+            };
+            let mut parts = line.splitn(3, ' ');
+            let day = parts
+                .next()
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(bad_line)?;
+            let part = parts
+                .next()
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(bad_line)?;
+            let answer = parts.next().ok_or_else(bad_line)?;
+            answers.insert((day, part), String::from(answer));
+        }
+        Ok(KnownAnswers { path, answers })
+    }
+
+    pub fn get(&self, day: u32, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u32, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u8, &str)> {
+        self.answers
+            .iter()
+            .map(|(&(day, part), answer)| (day, part, answer.as_str()))
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let text: String = self
+            .iter()
+            .map(|(day, part, answer)| format!("{} {} {}\n", day, part, answer))
+            .collect();
+        fs::write(&self.path, text)
+    }
+
+    pub fn verify(&self, day: u32, part: u8, answer: &Answer) -> Verdict {
+        let submittable = match answer.submittable() {
+            None => return Verdict::Unverifiable,
+            Some(s) => s,
+        };
+        match self.get(day, part) {
+            None => Verdict::Unknown,
+            Some(known) if known == submittable => Verdict::Correct,
+            Some(known) => Verdict::Wrong(String::from(known)),
+        }
+    }
+}
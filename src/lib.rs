@@ -1,4 +1,10 @@
+pub mod alloc;
+pub mod answer;
+pub mod arith;
+pub mod bits;
+pub mod cache;
 pub mod client;
+pub mod dashboard;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -24,3 +30,24 @@ pub mod day22;
 pub mod day23;
 pub mod day24;
 pub mod day25;
+pub mod export;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod known;
+pub mod memo;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod params;
+pub mod profile;
+pub mod progress;
+pub mod readme;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod sim;
+pub mod stepper;
+pub mod unlock;
+pub mod variants;
+pub mod vault;
@@ -1,16 +1,633 @@
-use std::time::Instant;
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process,
+    sync::Arc,
+    time::Duration,
+};
 
-use adventofcode::client;
-use adventofcode::day25 as day;
+use adventofcode::{
+    alloc::PeakAlloc,
+    cache::{self, ResultCache},
+    client::SubmitOutcome,
+    dashboard, day13, export, input,
+    known::{self, KnownAnswers, Verdict},
+    params::Params,
+    profile, progress, readme,
+    report::{Entry, Report, Status},
+    runner::{self, Failure, PartResult},
+    scaffold, sim,
+    stepper::Stepper,
+    unlock::{self, SystemClock},
+    variants,
+    vault::Vault,
+};
+use tracing_subscriber::EnvFilter;
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
+
+fn read_input(day: u32, path: Option<&str>) -> String {
+    let text = match path {
+        Some(path) => fs::read_to_string(path).unwrap(),
+        None => return find_day(day).input().unwrap(),
+    };
+    match input::sanitize(&text) {
+        Ok(text) => text,
+        Err(e) => panic!("bad input in {}: {}", path.unwrap(), e),
+    }
+}
+
+fn flag<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+// render DAY OUT.(png|gif) [--input FILE] [--steps N] [--scale N]
+fn render(args: &[String]) {
+    let usage = "render takes DAY OUT.(png|gif) [--input FILE] [--steps N] [--scale N]";
+    let day = day_arg(args, usage);
+    let out = positional(args, 1).unwrap_or_else(|| usage_error(usage));
+    let text = read_input(day, flag(args, "--input"));
+    let steps: usize = flag(args, "--steps").map_or(100, |s| s.parse().unwrap());
+    let scale: usize = flag(args, "--scale").map_or(4, |s| s.parse().unwrap());
+    let is_gif = out.ends_with(".gif");
+
+    if day == 13 {
+        if let Err(e) = export::write_png(out, &day13::paper(&text), scale) {
+            println!("not rendered: {}", e);
+        }
+        return;
+    }
+
+    let mut simulation = sim::for_day(day, &text)
+        .unwrap_or_else(|| usage_error(format!("day {} has no simulation", day)));
+    let mut gif = export::GifRecorder::new(scale, 100);
+    gif.record(&simulation.frame());
+    for _ in 0..steps {
+        let changed = simulation.step();
+        if is_gif {
+            gif.record(&simulation.frame());
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let res = if is_gif {
+        gif.write(out)
+    } else {
+        export::write_png(out, &simulation.frame(), scale)
+    };
+    if let Err(e) = res {
+        println!("not rendered: {}", e);
+    }
+}
+
+// step DAY [--input FILE]
+fn step(args: &[String]) {
+    let day = day_arg(args, "step takes DAY [--input FILE]");
+    let text = read_input(day, flag(args, "--input"));
+    let simulation = sim::for_day(day, &text)
+        .unwrap_or_else(|| usage_error(format!("day {} has no simulation", day)));
+    Stepper::new(simulation)
+        .run(io::stdin().lock(), io::stdout())
+        .unwrap();
+}
+
+// for command line mistakes, which shouldn't look like a crash
+fn usage_error(msg: impl Display) -> ! {
+    eprintln!("error: {}", msg);
+    process::exit(2)
+}
+
+// the argument at i unless it's missing or a flag
+fn positional(args: &[String], i: usize) -> Option<&str> {
+    args.get(i)
+        .filter(|a| !a.starts_with("--"))
+        .map(String::as_str)
+}
+
+// the DAY the command starts with
+fn day_arg(args: &[String], usage: &str) -> u32 {
+    match positional(args, 0).map(str::parse) {
+        Some(Ok(day)) => day,
+        _ => usage_error(usage),
+    }
+}
+
+fn find_day(day: u32) -> &'static runner::Day {
+    runner::find(day).unwrap_or_else(|| usage_error(format!("there is no day {}", day)))
+}
+
+fn parts(args: &[String]) -> Vec<u8> {
+    match flag(args, "--part") {
+        Some(part) => match part.parse() {
+            Ok(part @ 1..=2) => vec![part],
+            _ => usage_error(format!("--part should be 1 or 2, not {:?}", part)),
+        },
+        None => vec![1, 2],
+    }
+}
+
+fn days(args: &[String]) -> Vec<&'static runner::Day> {
+    let days: Vec<u32> = args
+        .iter()
+        .take_while(|a| !a.starts_with("--"))
+        .map(|a| {
+            a.parse()
+                .unwrap_or_else(|_| usage_error(format!("{:?} isn't a day", a)))
+        })
+        .collect();
+    if days.is_empty() {
+        return runner::DAYS.iter().collect();
+    }
+    days.iter().map(|&d| find_day(d)).collect()
+}
+
+// every --param name=value, checked against what the selected days declare
+fn params(args: &[String], days: &[&runner::Day]) -> Params {
+    let mut params = Params::new();
+    for (i, arg) in args.iter().enumerate() {
+        if arg != "--param" {
+            continue;
+        }
+        let value = args
+            .get(i + 1)
+            .unwrap_or_else(|| usage_error("--param needs name=value"));
+        let given = value.parse::<Params>().unwrap_or_else(|e| usage_error(e));
+        for (name, value) in given.iter() {
+            params.set(name, value);
+        }
+    }
+    // only the parameters of the parts that are going to run
+    let parts = parts(args);
+    let usable = || {
+        days.iter().flat_map(|&d| {
+            d.params
+                .iter()
+                .filter(|p| p.parts.iter().any(|part| parts.contains(part)))
+                .map(move |p| (d, p))
+        })
+    };
+    for name in params.names() {
+        if !usable().any(|(_, p)| p.name == name) {
+            let known: Vec<String> = usable()
+                .map(|(d, p)| format!("day{:02}:{}={}", d.day, p.name, p.default))
+                .collect();
+            usage_error(format!(
+                "no selected day and part has a parameter {:?}, there are: {}",
+                name,
+                if known.is_empty() {
+                    String::from("none")
+                } else {
+                    known.join(" ")
+                }
+            ));
+        }
+    }
+    for day in days.iter() {
+        if let Err(e) = params.check(day.params) {
+            usage_error(format!("day{:02}: {}", day.day, e));
+        }
+    }
+    params
+}
+
+fn timeout(args: &[String]) -> Option<Duration> {
+    flag(args, "--timeout")
+        .map(|s| Duration::from_secs_f64(s.parse().expect("timeout should be in seconds")))
+}
+
+// a progress line on stderr while the part runs, wiped once it's done
+fn with_progress_line<T>(label: String, f: impl FnOnce() -> T) -> T {
+    if !io::stderr().is_terminal() {
+        return f();
+    }
+    let res = progress::with_reporter(Arc::new(move |p| eprint!("\r\x1b[K{}: {}", label, p)), f);
+    eprint!("\r\x1b[K");
+    res
+}
+
+// the report goes to stdout for "--json -", plain lines are printed otherwise
+fn write_report(args: &[String], report: &Report) {
+    match flag(args, "--json") {
+        Some("-") => println!("{}", report.to_json()),
+        Some(path) => report.write(path).unwrap(),
+        None => (),
+    }
+}
+
+// run [DAY...] [--part N] [--param NAME=VALUE]... [--profile NAME|all] [--timeout SECS]
+//     [--json FILE] [--force]
+fn run(args: &[String]) {
+    let params = params(args, &days(args));
+    let force = args.iter().any(|a| a == "--force");
+    let mut cache = ResultCache::load(cache::DEFAULT_PATH).unwrap();
+    let profiles = profile::select(flag(args, "--profile").unwrap_or(profile::DEFAULT)).unwrap();
+    let labeled = profiles.len() > 1 || profiles.iter().any(|p| !p.is_default());
+    let quiet = flag(args, "--json") == Some("-");
+    let mut report = Report::new("run");
+    let mut disagreements = Vec::new();
+
+    for profile in profiles.iter() {
+        let known = KnownAnswers::load(profile.answers_path()).unwrap();
+        let label = if labeled {
+            format!("[{}] ", profile.name)
+        } else {
+            String::new()
+        };
+        for day in days(args) {
+            let text = day
+                .input_for(profile, &SystemClock)
+                .map_err(|e| Failure::Error(format!("can't load the input: {}", e)));
+            for &part in parts(args).iter() {
+                let res = match &text {
+                    Ok(text) => with_progress_line(
+                        format!("{}day{:02} res{}", label, day.day, part),
+                        || day.run_cached(part, text, &params, timeout(args), &mut cache, force),
+                    ),
+                    Err(e) => Err(e.clone()),
+                };
+                let line = match &res {
+                    Ok(result) => {
+                        // known answers only hold for the default parameters
+                        let overrides = params.overrides(day.params, part);
+                        let verdict = if overrides.is_empty() {
+                            runner::verify(result, &known).to_string()
+                        } else {
+                            format!("with {}", overrides)
+                        };
+                        let line = format!(
+                            "{}day{:02} res{}({}{:.3}s): {} [{}]",
+                            label,
+                            day.day,
+                            part,
+                            if result.cached { "cached, " } else { "" },
+                            result.elapsed.as_secs_f64(),
+                            result.answer,
+                            verdict
+                        );
+                        if overrides.is_empty() {
+                            if let Verdict::Wrong(_) = runner::verify(result, &known) {
+                                disagreements.push(line.clone());
+                            }
+                        }
+                        line
+                    }
+                    Err(failure) => format!("{}day{:02} res{}: {}", label, day.day, part, failure),
+                };
+                if !quiet {
+                    println!("{}", line);
+                }
+                let mut entry = Entry::new(day.day, part, &res);
+                if labeled {
+                    entry.profile = Some(profile.name.clone());
+                }
+                report.entries.push(entry);
+            }
+        }
+    }
+
+    if !quiet && !disagreements.is_empty() {
+        println!(
+            "\n{} answers disagree with the known ones:",
+            disagreements.len()
+        );
+        for line in disagreements {
+            println!("  {}", line);
+        }
+    }
+    cache.save().unwrap();
+    write_report(args, &report);
+}
+
+// bench [DAY...] [--part N] [--param NAME=VALUE]... [--runs N] [--timeout SECS] [--json FILE]
+fn bench(args: &[String]) {
+    let params = params(args, &days(args));
+    let runs: usize = flag(args, "--runs").map_or(10, |s| s.parse().unwrap());
+    let quiet = flag(args, "--json") == Some("-");
+    let mut report = Report::new("bench");
+
+    for day in days(args) {
+        let text = day
+            .input()
+            .map_err(|e| Failure::Error(format!("can't load the input: {}", e)));
+        for &part in parts(args).iter() {
+            let mut entry: Option<Entry> = None;
+            for _ in 0..runs.max(1) {
+                let res = match &text {
+                    Ok(text) => {
+                        with_progress_line(format!("day{:02} res{}", day.day, part), || {
+                            day.run_guarded(part, text, &params, timeout(args))
+                        })
+                    }
+                    Err(e) => Err(e.clone()),
+                };
+                let run = Entry::new(day.day, part, &res);
+                let failed = run.status != Status::Ok;
+                match entry.as_mut() {
+                    Some(entry) => entry.add_run(run),
+                    None => entry = Some(run),
+                }
+                if failed {
+                    break;
+                }
+            }
+            let entry = entry.unwrap();
+            if !quiet {
+                match (entry.time_ms, entry.min_time_ms, &entry.error) {
+                    (Some(mean), Some(min), _) => println!(
+                        "day{:02} res{}: mean {:.3}ms, min {:.3}ms over {} runs",
+                        day.day, part, mean, min, entry.runs
+                    ),
+                    (_, _, error) => println!(
+                        "day{:02} res{}: {}",
+                        day.day,
+                        part,
+                        error.as_deref().unwrap_or("")
+                    ),
+                }
+            }
+            report.entries.push(entry);
+        }
+    }
+    write_report(args, &report);
+}
+
+// compare --day N [--part N] [--runs N] [--timeout SECS] [--input FILE]
+fn compare(args: &[String]) {
+    let day: u32 = match flag(args, "--day").map(str::parse) {
+        Some(Ok(day)) => day,
+        _ => usage_error("compare needs --day N"),
+    };
+    let runs: usize = flag(args, "--runs").map_or(1, |s| s.parse().unwrap());
+    let day = find_day(day);
+    let text = read_input(day.day, flag(args, "--input"));
+
+    for &part in parts(args).iter() {
+        let mut results = Vec::new();
+        for variant in variants::for_part(day, part) {
+            let mut best: Option<PartResult> = None;
+            let mut failure = None;
+            for _ in 0..runs.max(1) {
+                let label = format!("day{:02} res{} {}", day.day, part, variant.name);
+                match with_progress_line(label, || day.run_variant(&variant, &text, timeout(args)))
+                {
+                    Ok(result) => {
+                        if best.as_ref().is_none_or(|b| result.elapsed < b.elapsed) {
+                            best = Some(result);
+                        }
+                    }
+                    Err(e) => {
+                        failure = Some(e);
+                        break;
+                    }
+                }
+            }
+            results.push((
+                variant.name,
+                best.filter(|_| failure.is_none()).ok_or(failure),
+            ));
+        }
+
+        let fastest = results
+            .iter()
+            .filter_map(|(_, r)| r.as_ref().ok())
+            .map(|r| r.elapsed)
+            .min();
+        let width = results.iter().map(|(name, _)| name.len()).max().unwrap();
+        println!("day{:02} res{}:", day.day, part);
+        for (name, res) in results.iter() {
+            match res {
+                Ok(result) => println!(
+                    "  {:width$}  {:>10.3}ms  {:>7.2}x  {}",
+                    name,
+                    result.elapsed.as_secs_f64() * 1000.0,
+                    result.elapsed.as_secs_f64() / fastest.unwrap().as_secs_f64().max(1e-9),
+                    result.answer,
+                    width = width
+                ),
+                Err(failure) => println!(
+                    "  {:width$}  {}",
+                    name,
+                    failure.as_ref().unwrap(),
+                    width = width
+                ),
+            }
+        }
+
+        let answers: Vec<_> = results
+            .iter()
+            .filter_map(|(_, r)| r.as_ref().ok())
+            .map(|r| &r.answer.value)
+            .collect();
+        if results.len() == 1 {
+            println!("  no other variants registered");
+        } else if answers.len() < results.len() {
+            println!("  some variants failed");
+        } else if answers.iter().all(|a| *a == answers[0]) {
+            println!("  all {} variants agree", answers.len());
+        } else {
+            println!("  variants DISAGREE");
+        }
+    }
+}
+
+// final frames of the days worth looking at, for the ones with a cached input
+fn dashboard_images(scale: usize) -> Vec<(u32, Vec<u8>)> {
+    let mut images = Vec::new();
+    for (day, steps) in [(13, 0), (20, 50), (25, 1000)] {
+        let day = runner::find(day).unwrap();
+        if !day.has_input(&profile::default().unwrap()) {
+            continue;
+        }
+        let text = day.input().unwrap();
+        let png = if day.day == 13 {
+            export::png_bytes(&day13::paper(&text), scale)
+        } else {
+            let mut simulation = sim::for_day(day.day, &text).unwrap();
+            for _ in 0..steps {
+                if !simulation.step() {
+                    break;
+                }
+            }
+            export::png_bytes(&simulation.frame(), scale)
+        };
+        images.push((day.day, png.unwrap()));
+    }
+    images
+}
+
+// dashboard REPORT.json OUT.html [--scale N]
+fn dashboard(args: &[String]) {
+    let usage = "dashboard takes REPORT.json OUT.html [--scale N]";
+    let (report, out) = match (positional(args, 0), positional(args, 1)) {
+        (Some(report), Some(out)) => (report, out),
+        _ => usage_error(usage),
+    };
+    let report = Report::load(report).unwrap();
+    let known = KnownAnswers::load(known::DEFAULT_PATH).unwrap();
+    let scale: usize = flag(args, "--scale").map_or(2, |s| s.parse().unwrap());
+    let out = Path::new(out);
+    let page_dir = out.parent().unwrap_or(Path::new("."));
+    let html = dashboard::render(&report, &known, &dashboard_images(scale), page_dir);
+    fs::write(out, html).unwrap();
+}
+
+// readme [--report REPORT.json] [--readme FILE]
+fn readme(args: &[String]) {
+    let path = flag(args, "--readme").unwrap_or("README.md");
+    let known = KnownAnswers::load(known::DEFAULT_PATH).unwrap();
+    let report = flag(args, "--report").map(|path| Report::load(path).unwrap());
+    let table = readme::progress_table(&known, report.as_ref());
+    let text = readme::update(&fs::read_to_string(path).unwrap(), &table).unwrap();
+    fs::write(path, text).unwrap();
+}
+
+// scaffold --year Y --day N [--title T]
+fn scaffold(args: &[String]) {
+    let year: u32 = match flag(args, "--year").map_or(Ok(2021), str::parse) {
+        Ok(year) => year,
+        Err(_) => usage_error("--year should be a number"),
+    };
+    let day: u32 = match flag(args, "--day").map(str::parse) {
+        Some(Ok(day)) => day,
+        _ => usage_error("scaffold needs --day N"),
+    };
+    let title = flag(args, "--title").map_or_else(|| format!("Day {}", day), String::from);
+    match scaffold::scaffold(Path::new("."), year, day, &title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("wrote {}", path.display());
+            }
+        }
+        Err(e) => println!("not scaffolded: {}", e),
+    }
+}
+
+// mock-server [--port N] [--fixtures DIR] [--session S] [--cooldown SECS]
+#[cfg(feature = "mock")]
+fn mock_server(args: &[String]) {
+    use adventofcode::mock::MockServer;
+
+    let port: u16 = flag(args, "--port").map_or(8080, |s| s.parse().unwrap());
+    let dir = flag(args, "--fixtures").unwrap_or("fixtures");
+    let session = flag(args, "--session").unwrap_or("mock");
+    let server = MockServer::start(port, dir, session).unwrap();
+    if let Some(cooldown) = flag(args, "--cooldown") {
+        server.set_cooldown(Duration::from_secs_f64(cooldown.parse().unwrap()));
+    }
+    println!("serving {} on {}", dir, server.base_url());
+    println!("AOC_BASE_URL={} AOC_SESSION={}", server.base_url(), session);
+    server.wait();
+}
+
+// fetch DAY [--wait]
+fn fetch(args: &[String]) {
+    let day = find_day(day_arg(args, "fetch takes DAY [--wait]"));
+    let res = if args.iter().any(|a| a == "--wait") {
+        let res = day.wait_for_input(&SystemClock, |left| {
+            eprint!(
+                "\rday {} unlocks in {} ",
+                day.day,
+                unlock::format_countdown(left)
+            );
+        });
+        eprintln!();
+        res
+    } else {
+        day.input()
+    };
+    match res {
+        Ok(_) => println!("day {} is cached", day.day),
+        Err(e) => println!("not fetched: {}", e),
+    }
+}
+
+// vault keygen | vault import [--profile NAME|all]
+fn vault(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("keygen") => println!("{}", Vault::generate_key()),
+        Some("import") => {
+            let vault = Vault::load().unwrap().expect("no vault key configured");
+            let profiles =
+                profile::select(flag(args, "--profile").unwrap_or(profile::DEFAULT)).unwrap();
+            for profile in profiles.iter() {
+                for day in runner::DAYS.iter() {
+                    let path = day.input_path_for(profile);
+                    if !path.exists() {
+                        continue;
+                    }
+                    let text = input::sanitize(&fs::read_to_string(&path).unwrap()).unwrap();
+                    let vault_path = day.vault_path_for(profile);
+                    vault.write(&vault_path, &text).unwrap();
+                    println!("{} -> {}", path.display(), vault_path.display());
+                }
+            }
+        }
+        _ => println!("usage: vault keygen | vault import [--profile NAME|all]"),
+    }
+}
+
+// submit DAY PART
+fn submit(args: &[String]) {
+    let (day, part) = match (
+        args.first().map(|d| d.parse()),
+        args.get(1).map(|p| p.parse()),
+    ) {
+        (Some(Ok(day)), Some(Ok(part @ 1..=2))) => (day, part),
+        _ => usage_error("submit takes DAY PART, PART being 1 or 2"),
+    };
+    let day = find_day(day);
+    let mut known = KnownAnswers::load(known::DEFAULT_PATH).unwrap();
+
+    let result = match day.run_guarded(part, &day.input().unwrap(), &Params::new(), None) {
+        Ok(result) => result,
+        Err(e) => {
+            println!("not submitted: {}", e);
+            return;
+        }
+    };
+    println!("answer: {}", result.answer);
+    match runner::submit(day, &result, &mut known) {
+        Ok(SubmitOutcome::Correct) => println!("that's the right answer"),
+        Ok(SubmitOutcome::Wrong(Some(hint))) => println!("wrong answer, {}", hint),
+        Ok(SubmitOutcome::Wrong(None)) => println!("wrong answer"),
+        Ok(SubmitOutcome::TooSoon(wait)) => println!("too soon, {} left to wait", wait),
+        Ok(SubmitOutcome::AlreadySolved) => println!("already solved"),
+        Ok(SubmitOutcome::Unexpected(page)) => println!("unexpected response: {}", page),
+        Err(e) => println!("not submitted: {}", e),
+    }
+}
 
 fn main() {
-    let text = client::fetch(day::URL);
-    //     let text = "";
-    let now = Instant::now();
-    let res1 = day::solve1(&text);
-    println!("res1({}s): {}", now.elapsed().as_secs(), res1);
-
-    let now = Instant::now();
-    let res2 = day::solve2(&text);
-    println!("res2({}s): {}", now.elapsed().as_secs(), res2);
+    if let Ok(filter) = env::var(progress::LOG_VAR) {
+        tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::new(filter))
+            .with_writer(io::stderr)
+            .init();
+    }
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("render") => render(&args[1..]),
+        Some("step") => step(&args[1..]),
+        Some("run") => run(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("compare") => compare(&args[1..]),
+        Some("dashboard") => dashboard(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        #[cfg(feature = "mock")]
+        Some("mock-server") => mock_server(&args[1..]),
+        Some("readme") => readme(&args[1..]),
+        Some("scaffold") => scaffold(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("vault") => vault(&args[1..]),
+        _ => run(&[String::from("25")]),
+    }
 }
@@ -0,0 +1,74 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses, {} entries ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.entries,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // `compute` gets the memo back so it can recurse through it
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(v) = self.cache.get(&key) {
+            self.hits += 1;
+            return v.clone();
+        }
+        self.misses += 1;
+        let v = compute(self);
+        self.cache.insert(key, v.clone());
+        v
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::known::KnownAnswers;
+
+const LOGGED_OUT: &str = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+struct State {
+    dir: PathBuf,
+    session: String,
+    cooldown: Duration,
+    last_wrong: Option<Instant>,
+    solved: HashSet<(u32, u8)>,
+    fail_next: u32,
+    requests: Vec<String>,
+}
+
+// stands in for adventofcode.com, inputs come from `dayNN.txt`, puzzle pages from
+// `dayNN.html` and the right answers from `answers.txt` in the fixture directory
+pub struct MockServer {
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    // port 0 picks a free one, see base_url
+    pub fn start(port: u16, dir: impl AsRef<Path>, session: &str) -> io::Result<Self> {
+        let server = Server::http(("127.0.0.1", port)).map_err(io::Error::other)?;
+        let server = Arc::new(server);
+        let state = Arc::new(Mutex::new(State {
+            dir: dir.as_ref().to_path_buf(),
+            session: String::from(session),
+            cooldown: Duration::from_secs(60),
+            last_wrong: None,
+            solved: HashSet::new(),
+            fail_next: 0,
+            requests: Vec::new(),
+        }));
+
+        let handle = {
+            let (server, state) = (Arc::clone(&server), Arc::clone(&state));
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(request, &state);
+                }
+            })
+        };
+        Ok(MockServer {
+            server,
+            state,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn base_url(&self) -> String {
+        let port = self
+            .server
+            .server_addr()
+            .to_ip()
+            .map_or(0, |addr| addr.port());
+        format!("http://127.0.0.1:{}", port)
+    }
+
+    // how long a wrong answer blocks the next one
+    pub fn set_cooldown(&self, cooldown: Duration) {
+        self.state.lock().unwrap().cooldown = cooldown;
+    }
+
+    // the next `count` requests get a 503
+    pub fn fail_next(&self, count: u32) {
+        self.state.lock().unwrap().fail_next = count;
+    }
+
+    // "GET /2021/day/1/input" for every request so far
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn wait(mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn article(text: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>\n",
+        text
+    )
+}
+
+fn respond(request: Request, status: u16, body: String) {
+    let content_type = if body.starts_with("<!DOCTYPE") {
+        "text/html"
+    } else {
+        "text/plain"
+    };
+    let header = Header::from_bytes("Content-Type", content_type).unwrap();
+    let _ = request.respond(
+        Response::from_string(body)
+            .with_status_code(status)
+            .with_header(header),
+    );
+}
+
+fn has_session(request: &Request, session: &str) -> bool {
+    let cookie = format!("session={}", session);
+    request
+        .headers()
+        .iter()
+        .filter(|h| h.field.equiv("Cookie"))
+        .any(|h| h.value.as_str().split("; ").any(|c| c == cookie))
+}
+
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut res = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => res.push(b' '),
+            b'%' if i + 2 < bytes.len() => match u8::from_str_radix(&value[i + 1..i + 3], 16) {
+                Ok(b) => {
+                    res.push(b);
+                    i += 2;
+                }
+                Err(_) => res.push(b'%'),
+            },
+            b => res.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&res).into_owned()
+}
+
+fn form_value(body: &str, name: &str) -> Option<String> {
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| decode(value))
+}
+
+// "/2021/day/7/answer" -> (7, "answer")
+fn route(url: &str) -> Option<(u32, &str)> {
+    let mut parts = url.trim_start_matches('/').splitn(4, '/');
+    let _year = parts.next()?;
+    if parts.next()? != "day" {
+        return None;
+    }
+    let day = parts.next()?.parse().ok()?;
+    Some((day, parts.next().unwrap_or("")))
+}
+
+fn answer_page(state: &mut State, day: u32, level: u8, answer: &str) -> String {
+    if let Some(at) = state.last_wrong {
+        let since = at.elapsed();
+        if since < state.cooldown {
+            return article(&format!(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {}s left to wait.",
+                (state.cooldown - since).as_secs().max(1)
+            ));
+        }
+    }
+    if state.solved.contains(&(day, level)) {
+        return article(
+            "You don't seem to be solving the right level.  Did you already complete it?",
+        );
+    }
+
+    let known = KnownAnswers::load(state.dir.join("answers.txt")).ok();
+    match known.as_ref().and_then(|known| known.get(day, level)) {
+        Some(right) if right == answer => {
+            state.solved.insert((day, level));
+            article(
+                "That's the right answer!  You are one gold star closer to saving your vacation.",
+            )
+        }
+        right => {
+            state.last_wrong = Some(Instant::now());
+            let hint = match (
+                right.and_then(|r| r.parse::<i128>().ok()),
+                answer.parse::<i128>(),
+            ) {
+                (Some(right), Ok(answer)) if answer > right => "  your answer is too high.",
+                (Some(right), Ok(answer)) if answer < right => "  your answer is too low.",
+                _ => "",
+            };
+            article(&format!(
+                "That's not the right answer;{}  If you're stuck, make sure you're using the full input data.",
+                hint
+            ))
+        }
+    }
+}
+
+fn handle(mut request: Request, state: &Mutex<State>) {
+    let mut state = state.lock().unwrap();
+    let url = String::from(request.url());
+    state.requests.push(format!("{} {}", request.method(), url));
+
+    if state.fail_next > 0 {
+        state.fail_next -= 1;
+        return respond(request, 503, String::from("503 Service Unavailable\n"));
+    }
+    let (day, endpoint) = match route(&url) {
+        Some(route) => route,
+        None => return respond(request, 404, String::from("404 Not Found\n")),
+    };
+    let logged_in = has_session(&request, &state.session);
+
+    match (request.method(), endpoint) {
+        (Method::Get, "input") if !logged_in => respond(request, 400, String::from(LOGGED_OUT)),
+        (Method::Get, "input") => {
+            match fs::read_to_string(state.dir.join(format!("day{:02}.txt", day))) {
+                Ok(text) => respond(request, 200, text),
+                Err(_) => respond(request, 404, String::from("404 Not Found\n")),
+            }
+        }
+        (Method::Get, "") => {
+            match fs::read_to_string(state.dir.join(format!("day{:02}.html", day))) {
+                Ok(page) => respond(request, 200, page),
+                Err(_) => respond(request, 404, String::from("404 Not Found\n")),
+            }
+        }
+        (Method::Post, "answer") if !logged_in => respond(
+            request,
+            400,
+            article("To play, please identify yourself via one of these services."),
+        ),
+        (Method::Post, "answer") => {
+            let mut body = String::new();
+            let _ = request.as_reader().read_to_string(&mut body);
+            let level = form_value(&body, "level").and_then(|l| l.parse().ok());
+            let answer = form_value(&body, "answer");
+            match (level, answer) {
+                (Some(level), Some(answer)) => {
+                    let page = answer_page(&mut state, day, level, &answer);
+                    respond(request, 200, page)
+                }
+                _ => respond(request, 400, String::from("400 Bad Request\n")),
+            }
+        }
+        _ => respond(request, 404, String::from("404 Not Found\n")),
+    }
+}
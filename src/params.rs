@@ -0,0 +1,111 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    // the parts that read it, --param is refused when none of them runs
+    pub parts: &'static [u8],
+    pub default: i64,
+    // inclusive, so the day can convert the value without checking it again
+    pub min: i64,
+    pub max: i64,
+}
+
+// values given on the command line, anything missing falls back to the default
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, i64>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.values.insert(String::from(name), value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.values
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+    }
+
+    // what the command line gave for names the day declares, each within its range
+    pub fn check(&self, declared: &[Param]) -> Result<(), String> {
+        for param in declared.iter() {
+            match self.values.get(param.name) {
+                Some(&value) if value < param.min || value > param.max => {
+                    return Err(format!(
+                        "{}={} is out of range, it goes from {} to {}",
+                        param.name, value, param.min, param.max
+                    ))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // a day asking for a parameter it never declared is a bug in the day, names from
+    // the command line are checked against the declarations before anything runs
+    pub fn get(&self, declared: &[Param], name: &str) -> i64 {
+        let param = declared
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap_or_else(|| panic!("parameter {:?} isn't declared", name));
+        self.values.get(name).copied().unwrap_or(param.default)
+    }
+
+    // only what differs from the defaults for this part, empty when nothing does
+    pub fn overrides(&self, declared: &[Param], part: u8) -> Params {
+        let values = declared
+            .iter()
+            .filter(|p| p.parts.contains(&part))
+            .filter_map(|p| match self.values.get(p.name) {
+                Some(&value) if value != p.default => Some((String::from(p.name), value)),
+                _ => None,
+            })
+            .collect();
+        Params { values }
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+// "name=value"
+impl FromStr for Params {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut params = Params::new();
+        for pair in s.split(',').filter(|p| !p.is_empty()) {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected name=value, got {:?}", pair))?;
+            let value = value
+                .parse()
+                .map_err(|e| format!("bad value for {}: {}", name, e))?;
+            params.set(name, value);
+        }
+        Ok(params)
+    }
+}
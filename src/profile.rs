@@ -0,0 +1,123 @@
+use std::{env, fs, io, path::PathBuf};
+
+use crate::{client, known, runner::INPUT_DIR, vault::VAULT_DIR};
+
+// one profile per line: "NAME SESSION", keep it out of git
+pub const PROFILES_PATH: &str = "profiles.txt";
+pub const DEFAULT: &str = "default";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub session: String,
+}
+
+impl Profile {
+    // the session from the environment, its files stay where they were before profiles
+    pub fn default_profile() -> Self {
+        Profile {
+            name: String::from(DEFAULT),
+            session: env::var(client::SESSION_VAR).unwrap_or_default(),
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT
+    }
+
+    pub fn input_dir(&self) -> PathBuf {
+        if self.is_default() {
+            PathBuf::from(INPUT_DIR)
+        } else {
+            PathBuf::from(INPUT_DIR).join(&self.name)
+        }
+    }
+
+    pub fn vault_dir(&self) -> PathBuf {
+        if self.is_default() {
+            PathBuf::from(VAULT_DIR)
+        } else {
+            PathBuf::from(VAULT_DIR).join(&self.name)
+        }
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        if self.is_default() {
+            PathBuf::from(known::DEFAULT_PATH)
+        } else {
+            PathBuf::from("answers").join(format!("{}.txt", self.name))
+        }
+    }
+}
+
+// names end up in paths, so nothing that could step out of inputs/ or the vault
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "all"
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// the default profile comes first unless the file has its own "default" line
+pub fn load() -> io::Result<Vec<Profile>> {
+    let text = match fs::read_to_string(PROFILES_PATH) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    let mut profiles = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, session) = line.split_once(' ').ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}:{}: expected NAME SESSION", PROFILES_PATH, i + 1),
+            )
+        })?;
+        if !valid_name(name) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{}:{}: {:?} isn't a profile name, use letters, digits, _ and -",
+                    PROFILES_PATH,
+                    i + 1,
+                    name
+                ),
+            ));
+        }
+        profiles.push(Profile {
+            name: String::from(name),
+            session: String::from(session.trim()),
+        });
+    }
+    if !profiles.iter().any(Profile::is_default) {
+        profiles.insert(0, Profile::default_profile());
+    }
+    Ok(profiles)
+}
+
+// the "default" line of the file if it has one, the environment's session otherwise
+pub fn default() -> io::Result<Profile> {
+    Ok(load()?
+        .into_iter()
+        .find(Profile::is_default)
+        .unwrap_or_else(Profile::default_profile))
+}
+
+// a profile name, or "all"
+pub fn select(name: &str) -> Result<Vec<Profile>, String> {
+    let profiles = load().map_err(|e| e.to_string())?;
+    if name == "all" {
+        return Ok(profiles);
+    }
+    profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .map(|p| vec![p])
+        .ok_or_else(|| format!("no profile named {:?} in {}", name, PROFILES_PATH))
+}
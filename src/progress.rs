@@ -0,0 +1,118 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tracing::Level;
+
+// tracing output goes to stderr when set, e.g. AOC_LOG=progress=info
+pub const LOG_VAR: &str = "AOC_LOG";
+
+// how often a solver's reports get through, they are dropped in between
+pub const INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub what: &'static str,
+    pub done: u64,
+    // known for the solvers that can tell how much work is left
+    pub total: Option<u64>,
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.total {
+            Some(total) if total > 0 => write!(
+                f,
+                "{} {}/{} ({}%)",
+                self.what,
+                self.done,
+                total,
+                self.done * 100 / total
+            ),
+            _ => write!(f, "{} {}", self.what, self.done),
+        }
+    }
+}
+
+pub type Reporter = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+#[derive(Clone)]
+struct Installed {
+    reporter: Reporter,
+    // cleared once with_reporter returns, so a solver left running after a
+    // timeout goes quiet
+    active: Arc<AtomicBool>,
+}
+
+struct State {
+    installed: Option<Installed>,
+    last: Option<Instant>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = const { RefCell::new(State { installed: None, last: None }) };
+    // whether STATE has a reporter, kept apart so report can skip the borrow
+    static LISTENING: Cell<bool> = const { Cell::new(false) };
+}
+
+// called by the solvers as often as they like, when nobody listens it's a thread
+// local lookup and the tracing level check, neither the clock nor the borrow
+pub fn report(what: &'static str, done: u64, total: Option<u64>) {
+    if !LISTENING.get() && !tracing::enabled!(target: "progress", Level::INFO) {
+        return;
+    }
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let finished = total == Some(done);
+        if !finished && state.last.is_some_and(|last| last.elapsed() < INTERVAL) {
+            return;
+        }
+        state.last = Some(Instant::now());
+
+        tracing::info!(target: "progress", done, total, "{}", what);
+        if let Some(installed) = &state.installed {
+            if installed.active.load(Ordering::Relaxed) {
+                (installed.reporter)(&Progress { what, done, total });
+            }
+        }
+    });
+}
+
+// runs f with the reporter getting the progress of the solvers it calls
+pub fn with_reporter<T>(reporter: Reporter, f: impl FnOnce() -> T) -> T {
+    let installed = Installed {
+        reporter,
+        active: Arc::new(AtomicBool::new(true)),
+    };
+    let active = installed.active.clone();
+    let prev = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.last = None;
+        state.installed.replace(installed)
+    });
+    LISTENING.set(true);
+    let res = f();
+    active.store(false, Ordering::Relaxed);
+    LISTENING.set(prev.is_some());
+    STATE.with(|state| state.borrow_mut().installed = prev);
+    res
+}
+
+// carries the reporter of this thread over to a solver thread
+pub fn propagate() -> impl FnOnce() + Send {
+    let installed = STATE.with(|state| state.borrow().installed.clone());
+    move || {
+        LISTENING.set(installed.is_some());
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.last = None;
+            state.installed = installed;
+        })
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::Write;
+
+use crate::{known::KnownAnswers, report::Report, runner};
+
+pub const START_MARKER: &str = "<!-- progress:start -->";
+pub const END_MARKER: &str = "<!-- progress:end -->";
+
+fn format_time(millis: f64) -> String {
+    if millis >= 1000.0 {
+        format!("{:.2}s", millis / 1000.0)
+    } else {
+        format!("{:.2}ms", millis)
+    }
+}
+
+// solved parts come from the known answers, runtimes from a run or bench report
+pub fn progress_table(known: &KnownAnswers, report: Option<&Report>) -> String {
+    let mut table = String::new();
+    table.push_str("| Day | Title | Solved | Runtime | Source |\n");
+    table.push_str("| ---: | --- | :---: | ---: | --- |\n");
+    for day in runner::DAYS.iter() {
+        let solved: String = (1..=2)
+            .map(|part| {
+                if known.get(day.day, part).is_some() {
+                    '★'
+                } else {
+                    '☆'
+                }
+            })
+            .collect();
+        let runtime = report
+            .map(|report| {
+                report
+                    .entries
+                    .iter()
+                    .filter(|e| e.day == day.day)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|entries| !entries.is_empty())
+            .and_then(|entries| entries.iter().map(|e| e.time_ms).sum::<Option<f64>>())
+            .map_or_else(|| String::from("-"), format_time);
+        let source = format!("src/day{:02}.rs", day.day);
+        writeln!(
+            table,
+            "| {} | [{}](https://adventofcode.com/2021/day/{}) | {} | {} | [{}]({}) |",
+            day.day, day.title, day.day, solved, runtime, source, source
+        )
+        .unwrap();
+    }
+    table
+}
+
+// only the text between the markers is replaced, the section is appended if they're missing
+pub fn update(readme: &str, table: &str) -> Result<String, String> {
+    let section = format!("{}\n{}{}", START_MARKER, table, END_MARKER);
+    match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => Ok(format!(
+            "{}{}{}",
+            &readme[..start],
+            section,
+            &readme[end + END_MARKER.len()..]
+        )),
+        (None, None) => Ok(format!("{}\n\n{}\n", readme.trim_end(), section)),
+        _ => Err(format!(
+            "the README has to have both {} and {} in that order",
+            START_MARKER, END_MARKER
+        )),
+    }
+}
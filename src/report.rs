@@ -0,0 +1,130 @@
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answer::Value,
+    runner::{Failure, PartResult},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+    Timeout,
+    Panic,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    // only set when the run covered other profiles than the default one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    pub day: u32,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub details: Option<String>,
+    // mean over all runs for bench
+    pub time_ms: Option<f64>,
+    pub min_time_ms: Option<f64>,
+    pub runs: usize,
+    pub memory_bytes: Option<u64>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub cached: bool,
+}
+
+impl Entry {
+    pub fn new(day: u32, part: u8, res: &Result<PartResult, Failure>) -> Self {
+        match res {
+            Ok(result) => {
+                let millis = result.elapsed.as_secs_f64() * 1000.0;
+                Entry {
+                    profile: None,
+                    day,
+                    part,
+                    status: Status::Ok,
+                    answer: Some(match &result.answer.value {
+                        Value::Art(art) => art.clone(),
+                        value => value.to_string(),
+                    }),
+                    details: result.answer.details.clone(),
+                    time_ms: Some(millis),
+                    min_time_ms: Some(millis),
+                    runs: 1,
+                    memory_bytes: result.memory,
+                    error: None,
+                    cached: result.cached,
+                }
+            }
+            Err(failure) => Entry {
+                profile: None,
+                day,
+                part,
+                status: match failure {
+                    Failure::Error(_) => Status::Error,
+                    Failure::Timeout(_) => Status::Timeout,
+                    Failure::Panic(_) => Status::Panic,
+                },
+                answer: None,
+                details: None,
+                time_ms: None,
+                min_time_ms: None,
+                runs: 0,
+                memory_bytes: None,
+                error: Some(failure.to_string()),
+                cached: false,
+            },
+        }
+    }
+
+    // folds in another run of the same part, a failed run makes the whole entry fail
+    pub fn add_run(&mut self, other: Entry) {
+        if other.status != Status::Ok {
+            *self = other;
+            return;
+        }
+        if self.status != Status::Ok {
+            return;
+        }
+        let (mean, other_time) = (self.time_ms.unwrap_or(0.0), other.time_ms.unwrap_or(0.0));
+        let runs = self.runs + 1;
+        self.time_ms = Some((mean * self.runs as f64 + other_time) / runs as f64);
+        if let Some(other_min) = other.min_time_ms {
+            self.min_time_ms = Some(self.min_time_ms.map_or(other_min, |min| min.min(other_min)));
+        }
+        self.memory_bytes = self.memory_bytes.max(other.memory_bytes);
+        self.runs = runs;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    // "run" or "bench"
+    pub command: String,
+    pub entries: Vec<Entry>,
+}
+
+impl Report {
+    pub fn new(command: &str) -> Self {
+        Report {
+            command: String::from(command),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_json() + "\n")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
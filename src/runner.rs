@@ -0,0 +1,409 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc,
+    answer::Answer,
+    arith::Overflow,
+    cache::ResultCache,
+    client::{self, SubmitOutcome},
+    input,
+    known::{KnownAnswers, Verdict},
+    params::{Param, Params},
+    profile::{self, Profile},
+    progress,
+    unlock::{self, Clock, SystemClock},
+    variants::Variant,
+    vault::{self, Vault},
+};
+
+pub const INPUT_DIR: &str = "inputs";
+
+pub struct Day {
+    pub day: u32,
+    pub url: &'static str,
+    pub title: &'static str,
+    // cached answers are only reused for the same version
+    pub version: &'static str,
+    // the ones --param can set, see params
+    pub params: &'static [Param],
+    pub parts: [fn(&str, &Params) -> Answer; 2],
+}
+
+impl Day {
+    pub fn answer_url(&self) -> String {
+        self.url.replace("/input", "/answer")
+    }
+
+    pub fn input_path_for(&self, profile: &Profile) -> PathBuf {
+        profile.input_dir().join(format!("day{:02}.txt", self.day))
+    }
+
+    pub fn vault_path_for(&self, profile: &Profile) -> PathBuf {
+        profile.vault_dir().join(format!("day{:02}.enc", self.day))
+    }
+
+    pub fn has_input(&self, profile: &Profile) -> bool {
+        self.input_path_for(profile).exists() || self.vault_path_for(profile).exists()
+    }
+
+    // the vault copy comes first, the plain one is still read when there's no key
+    fn cached_input(&self, profile: &Profile) -> io::Result<Option<String>> {
+        let vault = Vault::load()?;
+        let vault_path = self.vault_path_for(profile);
+        if let (Some(vault), true) = (&vault, vault_path.exists()) {
+            return vault.read(&vault_path).map(Some);
+        }
+        let path = self.input_path_for(profile);
+        if path.exists() {
+            return fs::read_to_string(path).map(Some);
+        }
+        if vault_path.exists() {
+            return Err(io::Error::other(format!(
+                "{} is encrypted, set {} or put the key in {}",
+                vault_path.display(),
+                vault::KEY_VAR,
+                vault::KEY_PATH
+            )));
+        }
+        Ok(None)
+    }
+
+    // goes to the vault when a key is set up, to the plain cache otherwise
+    pub fn store_input(&self, profile: &Profile, text: &str) -> io::Result<()> {
+        match Vault::load()? {
+            Some(vault) => vault.write(&self.vault_path_for(profile), text),
+            None => {
+                fs::create_dir_all(profile.input_dir())?;
+                fs::write(self.input_path_for(profile), text)
+            }
+        }
+    }
+
+    // "https://adventofcode.com/2021/day/7/input" -> 2021
+    pub fn year(&self) -> u32 {
+        self.url
+            .split('/')
+            .find_map(|part| part.parse().ok())
+            .unwrap_or(2021)
+    }
+
+    pub fn input(&self) -> io::Result<String> {
+        self.input_at(&SystemClock)
+    }
+
+    // fetched only once, later runs read the cached copy, error pages never get cached
+    // and a day that's still locked isn't asked for at all
+    pub fn input_at(&self, clock: &dyn Clock) -> io::Result<String> {
+        self.input_for(&profile::default()?, clock)
+    }
+
+    pub fn input_for(&self, profile: &Profile, clock: &dyn Clock) -> io::Result<String> {
+        if let Some(text) = self.cached_input(profile)? {
+            return Ok(input::sanitize(&text)?);
+        }
+        if let Some(left) = unlock::time_left(clock, self.year(), self.day) {
+            return Err(io::Error::other(format!(
+                "day {} unlocks in {}",
+                self.day,
+                unlock::format_countdown(left)
+            )));
+        }
+        let text = client::shared()
+            .get_as(self.url, &profile.session)
+            .map_err(io::Error::other)?;
+        let text = input::sanitize(&text)?;
+        self.store_input(profile, &text)?;
+        Ok(text)
+    }
+
+    // waits for the unlock, then fetches and caches the input right away
+    pub fn wait_for_input(
+        &self,
+        clock: &dyn Clock,
+        tick: impl FnMut(Duration),
+    ) -> io::Result<String> {
+        if !self.has_input(&profile::default()?) {
+            unlock::wait_for_unlock(clock, self.year(), self.day, tick);
+        }
+        self.input_at(clock)
+    }
+
+    pub fn run(&self, part: u8, text: &str, params: &Params) -> PartResult {
+        let solve = self.parts[part as usize - 1];
+        let _span = tracing::info_span!("solve", day = self.day, part).entered();
+        let now = Instant::now();
+        let (answer, memory) = alloc::measure(|| solve(text, params));
+        PartResult {
+            day: self.day,
+            part,
+            answer,
+            elapsed: now.elapsed(),
+            memory,
+            cached: false,
+        }
+    }
+
+    // runs the part on its own thread so a panic or a solver that never finishes
+    // doesn't take the whole run down, a timed out thread is left behind
+    pub fn run_guarded(
+        &self,
+        part: u8,
+        text: &str,
+        params: &Params,
+        timeout: Option<Duration>,
+    ) -> Result<PartResult, Failure> {
+        self.run_solver_guarded(part, self.parts[part as usize - 1], text, params, timeout)
+    }
+
+    pub fn run_variant(
+        &self,
+        variant: &Variant,
+        text: &str,
+        timeout: Option<Duration>,
+    ) -> Result<PartResult, Failure> {
+        self.run_solver_guarded(variant.part, variant.solve, text, &Params::new(), timeout)
+    }
+
+    fn run_solver_guarded(
+        &self,
+        part: u8,
+        solve: fn(&str, &Params) -> Answer,
+        text: &str,
+        params: &Params,
+        timeout: Option<Duration>,
+    ) -> Result<PartResult, Failure> {
+        let (day, text, params) = (self.day, String::from(text), params.clone());
+        let (sender, receiver) = mpsc::channel();
+        let install_reporter = progress::propagate();
+        let now = Instant::now();
+        thread::spawn(move || {
+            install_reporter();
+            let _span = tracing::info_span!("solve", day, part).entered();
+            let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                alloc::measure(|| solve(&text, &params))
+            }));
+            let _ = sender.send(res);
+        });
+
+        let res = match timeout {
+            Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+                mpsc::RecvTimeoutError::Timeout => {
+                    alloc::abandon();
+                    Failure::Timeout(timeout)
+                }
+                mpsc::RecvTimeoutError::Disconnected => {
+                    Failure::Panic(String::from("solver thread died"))
+                }
+            })?,
+            None => receiver
+                .recv()
+                .map_err(|_| Failure::Panic(String::from("solver thread died")))?,
+        };
+        let elapsed = now.elapsed();
+        let (answer, memory) = res.map_err(panic_failure)?;
+        Ok(PartResult {
+            day,
+            part,
+            answer,
+            elapsed,
+            memory,
+            cached: false,
+        })
+    }
+
+    // the cached answer when there's one for this input and VERSION, unless forced
+    pub fn run_cached(
+        &self,
+        part: u8,
+        text: &str,
+        params: &Params,
+        timeout: Option<Duration>,
+        cache: &mut ResultCache,
+        force: bool,
+    ) -> Result<PartResult, Failure> {
+        let overrides = params.overrides(self.params, part);
+        let mut version = String::from(self.version);
+        if !overrides.is_empty() {
+            version = format!("{}+{}", version, overrides);
+        }
+        // an unchecked build can cache a wrapped answer the checked one would reject
+        if cfg!(feature = "checked") {
+            version.push_str("+checked");
+        }
+        if !force {
+            if let Some(cached) = cache.get(self.day, part, &version, text) {
+                return Ok(PartResult {
+                    day: self.day,
+                    part,
+                    answer: cached.answer.clone(),
+                    elapsed: Duration::from_secs_f64(cached.time_ms / 1000.0),
+                    memory: None,
+                    cached: true,
+                });
+            }
+        }
+        let result = self.run_guarded(part, text, params, timeout)?;
+        cache.insert(
+            self.day,
+            part,
+            &version,
+            text,
+            result.answer.clone(),
+            result.elapsed,
+        );
+        Ok(result)
+    }
+}
+
+// an overflow caught by the checked feature is the solver's error rather than a panic
+fn panic_failure(payload: Box<dyn std::any::Any + Send>) -> Failure {
+    match payload.downcast::<Overflow>() {
+        Ok(overflow) => Failure::Error(overflow.to_string()),
+        Err(payload) => Failure::Panic(panic_message(payload)),
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        String::from(*s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+pub struct PartResult {
+    pub day: u32,
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    // peak bytes allocated by the solver, if the binary tracks allocations
+    pub memory: Option<u64>,
+    // served from the result cache, elapsed is the time it took back then
+    pub cached: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Error(String),
+    Timeout(Duration),
+    Panic(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Error(e) => write!(f, "error: {}", e),
+            Failure::Timeout(timeout) => write!(f, "timed out after {}s", timeout.as_secs_f64()),
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+macro_rules! day {
+    ($n:literal, $day:ident) => {
+        Day {
+            day: $n,
+            url: crate::$day::URL,
+            title: crate::$day::TITLE,
+            version: crate::$day::VERSION,
+            params: &[],
+            parts: [
+                |text, _| crate::$day::solve1(text).into(),
+                |text, _| crate::$day::solve2(text).into(),
+            ],
+        }
+    };
+    ($n:literal, $day:ident, params) => {
+        Day {
+            day: $n,
+            url: crate::$day::URL,
+            title: crate::$day::TITLE,
+            version: crate::$day::VERSION,
+            params: crate::$day::PARAMS,
+            parts: [
+                |text, params| crate::$day::solve1_with(text, params).into(),
+                |text, params| crate::$day::solve2_with(text, params).into(),
+            ],
+        }
+    };
+    // only part 1 has parameters
+    ($n:literal, $day:ident, params1) => {
+        Day {
+            day: $n,
+            url: crate::$day::URL,
+            title: crate::$day::TITLE,
+            version: crate::$day::VERSION,
+            params: crate::$day::PARAMS,
+            parts: [
+                |text, params| crate::$day::solve1_with(text, params).into(),
+                |text, _| crate::$day::solve2(text).into(),
+            ],
+        }
+    };
+}
+
+// "6 => day06(params)" for the days that declare PARAMS and solveN_with,
+// "11 => day11(params1)" when only solve1_with takes them
+macro_rules! days {
+    ($($n:literal => $day:ident $(($params:ident))?),* $(,)?) => {
+        pub const DAYS: &[Day] = &[$(day!($n, $day $(, $params)?),)*];
+    };
+}
+
+days!(
+    1 => day01, 2 => day02, 3 => day03, 4 => day04, 5 => day05,
+    6 => day06(params), 7 => day07, 8 => day08, 9 => day09, 10 => day10,
+    11 => day11(params1), 12 => day12, 13 => day13, 14 => day14(params), 15 => day15,
+    16 => day16, 17 => day17, 18 => day18, 19 => day19(params), 20 => day20(params),
+    21 => day21(params), 22 => day22(params1), 23 => day23, 24 => day24, 25 => day25,
+);
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+pub fn verify(result: &PartResult, known: &KnownAnswers) -> Verdict {
+    known.verify(result.day, result.part, &result.answer)
+}
+
+// answers already known to be right are never sent again, right ones get remembered
+pub fn submit(
+    day: &Day,
+    result: &PartResult,
+    known: &mut KnownAnswers,
+) -> Result<SubmitOutcome, String> {
+    let answer = result
+        .answer
+        .submittable()
+        .ok_or_else(|| String::from("this answer has to be read and submitted by hand"))?;
+    match known.verify(day.day, result.part, &result.answer) {
+        Verdict::Correct => return Ok(SubmitOutcome::AlreadySolved),
+        Verdict::Wrong(expected) => {
+            return Err(format!(
+                "{} is already known to be wrong, the answer is {}",
+                answer, expected
+            ))
+        }
+        _ => (),
+    }
+
+    let outcome =
+        client::submit(&day.answer_url(), result.part, &answer).map_err(|e| e.to_string())?;
+    if outcome == SubmitOutcome::Correct {
+        known.insert(day.day, result.part, answer);
+        known
+            .save()
+            .map_err(|e| format!("can't save the answer: {}", e))?;
+    }
+    Ok(outcome)
+}
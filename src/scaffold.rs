@@ -0,0 +1,105 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const TEMPLATE: &str = r#"#[allow(dead_code)]
+pub const URL: &str = "https://adventofcode.com/{year}/day/{day}/input";
+pub const TITLE: &str = "{title}";
+// bump it whenever a change can alter the answers, cached results get dropped
+pub const VERSION: &str = "1";
+
+#[allow(dead_code)]
+pub fn solve1(_text: &str) -> u64 {
+    todo!()
+}
+
+#[allow(dead_code)]
+pub fn solve2(_text: &str) -> u64 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = include_str!("../fixtures/day{nn}.txt");
+
+    #[test]
+    #[ignore = "paste the example into the fixture and fill in its answers"]
+    fn example() {
+        assert_eq!(super::solve1(EXAMPLE), 0);
+        assert_eq!(super::solve2(EXAMPLE), 0);
+    }
+}
+"#;
+
+pub const FIXTURE_DIR: &str = "fixtures";
+
+fn insert_module(lib: &str, module: &str) -> String {
+    let line = format!("pub mod {};", module);
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let pos = lines
+        .iter()
+        .position(|l| l.starts_with("pub mod ") && *l > line.as_str())
+        .unwrap_or(lines.len());
+    lines.insert(pos, &line);
+    lines.join("\n") + "\n"
+}
+
+fn register_day(runner: &str, day: u32, module: &str) -> Result<String, String> {
+    let start = runner
+        .find("days!(")
+        .ok_or_else(|| String::from("can't find the days! list in the runner"))?;
+    let end = start
+        + runner[start..]
+            .find("\n);")
+            .ok_or_else(|| String::from("can't find the end of the days! list"))?;
+    Ok(format!(
+        "{}\n    {} => {},{}",
+        &runner[..end],
+        day,
+        module,
+        &runner[end..]
+    ))
+}
+
+// creates src/dayNN.rs and its empty example fixture, then registers the module
+// in lib.rs and the runner, nothing is touched if either file is already there
+pub fn scaffold(root: &Path, year: u32, day: u32, title: &str) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {}, they go from 1 to 25", day));
+    }
+    if year < 2015 {
+        return Err(format!("there was no Advent of Code in {}", year));
+    }
+    let module = format!("day{:02}", day);
+    let source = root.join("src").join(format!("{}.rs", module));
+    let fixture = root.join(FIXTURE_DIR).join(format!("{}.txt", module));
+    for path in [&source, &fixture] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let runner_path = root.join("src/runner.rs");
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))
+    };
+    let lib = insert_module(&read(&lib_path)?, &module);
+    let runner = register_day(&read(&runner_path)?, day, &module)?;
+
+    let code = TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{nn}", &format!("{:02}", day))
+        .replace("{title}", &title.replace('"', "\\\""));
+    let write = |path: &Path, text: &str| {
+        fs::write(path, text).map_err(|e| format!("can't write {}: {}", path.display(), e))
+    };
+    fs::create_dir_all(root.join(FIXTURE_DIR)).map_err(|e| e.to_string())?;
+    write(&source, &code)?;
+    write(&fixture, "")?;
+    write(&lib_path, &lib)?;
+    write(&runner_path, &runner)?;
+    Ok(vec![source, fixture, lib_path, runner_path])
+}
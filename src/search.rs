@@ -0,0 +1,148 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+#[derive(PartialEq, Eq)]
+struct Node<C> {
+    idx: usize,
+    cost: C,
+    estimate: C,
+}
+
+impl<C: Ord> Ord for Node<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
+impl<C: Ord> PartialOrd for Node<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn reconstruct<N: Clone>(visited: &[(N, usize)], mut idx: usize) -> Vec<N> {
+    let mut path = vec![visited[idx].0.clone()];
+    while visited[idx].1 != idx {
+        idx = visited[idx].1;
+        path.push(visited[idx].0.clone());
+    }
+    path.reverse();
+    path
+}
+
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: &N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    // every visited state with the index of its parent, the start is its own parent
+    let mut visited: Vec<(N, usize)> = vec![(start.clone(), 0)];
+    let mut costs: Vec<C> = vec![C::default()];
+    let mut indexes: HashMap<N, usize> = HashMap::new();
+    indexes.insert(start.clone(), 0);
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Node {
+        idx: 0,
+        cost: C::default(),
+        estimate: heuristic(start),
+    });
+
+    while let Some(curr) = heap.pop() {
+        if curr.cost > costs[curr.idx] {
+            continue;
+        }
+        let state = visited[curr.idx].0.clone();
+        if success(&state) {
+            return Some((reconstruct(&visited, curr.idx), curr.cost));
+        }
+        for (next, step_cost) in successors(&state) {
+            let next_cost = curr.cost + step_cost;
+            let idx = match indexes.get(&next) {
+                Some(&idx) => {
+                    if costs[idx] <= next_cost {
+                        continue;
+                    }
+                    visited[idx].1 = curr.idx;
+                    costs[idx] = next_cost;
+                    idx
+                }
+                None => {
+                    let idx = visited.len();
+                    indexes.insert(next.clone(), idx);
+                    visited.push((next.clone(), curr.idx));
+                    costs.push(next_cost);
+                    idx
+                }
+            };
+            heap.push(Node {
+                idx,
+                cost: next_cost,
+                estimate: next_cost + heuristic(&next),
+            });
+        }
+    }
+
+    None
+}
+
+pub fn dijkstra<N, C, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+pub fn bfs<N, FN, IN, FS>(start: &N, mut successors: FN, mut success: FS) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited: Vec<(N, usize)> = vec![(start.clone(), 0)];
+    let mut indexes: HashMap<N, usize> = HashMap::new();
+    indexes.insert(start.clone(), 0);
+
+    let mut queue = VecDeque::from([0]);
+    while let Some(idx) = queue.pop_front() {
+        let state = visited[idx].0.clone();
+        if success(&state) {
+            let path = reconstruct(&visited, idx);
+            let steps = path.len() - 1;
+            return Some((path, steps));
+        }
+        for next in successors(&state) {
+            if indexes.contains_key(&next) {
+                continue;
+            }
+            let next_idx = visited.len();
+            indexes.insert(next.clone(), next_idx);
+            visited.push((next, idx));
+            queue.push_back(next_idx);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,24 @@
+use crate::{day11, day20, day23, day25, export::Rgb, grid::Grid};
+
+pub trait Simulation {
+    // returns false once the state stops changing
+    fn step(&mut self) -> bool;
+
+    fn frame(&self) -> Grid<Rgb>;
+
+    fn render(&self) -> String;
+
+    fn counters(&self) -> Vec<(&'static str, u64)>;
+
+    fn boxed_clone(&self) -> Box<dyn Simulation>;
+}
+
+pub fn for_day(day: u32, text: &str) -> Option<Box<dyn Simulation>> {
+    match day {
+        11 => Some(Box::new(day11::simulation(text))),
+        20 => Some(Box::new(day20::simulation(text))),
+        23 => Some(Box::new(day23::simulation(text))),
+        25 => Some(Box::new(day25::simulation(text))),
+        _ => None,
+    }
+}
@@ -0,0 +1,142 @@
+use std::io::{self, BufRead, Write};
+
+use crate::sim::Simulation;
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const HELP: &str = "[enter/n] next  [p] back  [g N] go to step N  [q] quit";
+
+enum Command {
+    Next,
+    Back,
+    Jump(usize),
+    Quit,
+}
+
+fn parse_command(line: &str) -> Result<Command, String> {
+    let mut parts = line.split_whitespace();
+    match parts.next() {
+        None | Some("n") => Ok(Command::Next),
+        Some("p") => Ok(Command::Back),
+        Some("q") => Ok(Command::Quit),
+        Some("g") => parts
+            .next()
+            .ok_or_else(|| String::from("g needs a step number"))?
+            .parse()
+            .map(Command::Jump)
+            .map_err(|e| format!("bad step number: {}", e)),
+        Some(cmd) => Err(format!("unknown command {:?}", cmd)),
+    }
+}
+
+pub struct Stepper {
+    // the state after each step, the initial one is at 0
+    history: Vec<Box<dyn Simulation>>,
+    curr: usize,
+    // the step after which the state doesn't change anymore
+    last: Option<usize>,
+}
+
+impl Stepper {
+    pub fn new(simulation: Box<dyn Simulation>) -> Self {
+        Stepper {
+            history: vec![simulation],
+            curr: 0,
+            last: None,
+        }
+    }
+
+    pub fn curr(&self) -> usize {
+        self.curr
+    }
+
+    pub fn state(&self) -> &dyn Simulation {
+        self.history[self.curr].as_ref()
+    }
+
+    pub fn forward(&mut self) -> bool {
+        if self.curr + 1 < self.history.len() {
+            self.curr += 1;
+            return true;
+        }
+        if self.last == Some(self.curr) {
+            return false;
+        }
+        let mut next = self.history[self.curr].boxed_clone();
+        if !next.step() {
+            self.last = Some(self.curr);
+            return false;
+        }
+        self.history.push(next);
+        self.curr += 1;
+        true
+    }
+
+    pub fn back(&mut self) -> bool {
+        if self.curr == 0 {
+            return false;
+        }
+        self.curr -= 1;
+        true
+    }
+
+    pub fn jump(&mut self, step: usize) -> bool {
+        if step < self.history.len() {
+            self.curr = step;
+            return true;
+        }
+        self.curr = self.history.len() - 1;
+        while self.curr < step {
+            if !self.forward() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn draw(&self, out: &mut impl Write, status: &str) -> io::Result<()> {
+        let state = self.state();
+        write!(out, "{}", CLEAR)?;
+        write!(out, "step {}", self.curr)?;
+        if self.last == Some(self.curr) {
+            write!(out, " (final)")?;
+        }
+        writeln!(out, "\n")?;
+        writeln!(out, "{}", state.render())?;
+        for (name, value) in state.counters() {
+            writeln!(out, "{}: {}", name, value)?;
+        }
+        writeln!(out)?;
+        if !status.is_empty() {
+            writeln!(out, "{}", status)?;
+        }
+        write!(out, "{} > ", HELP)?;
+        out.flush()
+    }
+
+    pub fn run(&mut self, mut input: impl BufRead, mut out: impl Write) -> io::Result<()> {
+        let mut status = String::new();
+        loop {
+            self.draw(&mut out, &status)?;
+            status.clear();
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                return Ok(());
+            }
+            let moved = match parse_command(&line) {
+                Ok(Command::Next) => self.forward(),
+                Ok(Command::Back) => self.back(),
+                Ok(Command::Jump(step)) => self.jump(step),
+                Ok(Command::Quit) => return Ok(()),
+                Err(e) => {
+                    status = e;
+                    continue;
+                }
+            };
+            if !moved {
+                status = String::from("can't go any further");
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+// puzzles unlock at midnight US-Eastern, which is always EST (UTC-5) in December
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+// days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+// None once the day is open
+pub fn time_left(clock: &dyn Clock, year: u32, day: u32) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(clock.now())
+        .ok()
+        .filter(|left| !left.is_zero())
+}
+
+pub fn format_countdown(left: Duration) -> String {
+    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let clock = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {}", days, clock)
+    } else {
+        clock
+    }
+}
+
+// sleeps until the day unlocks, `tick` gets the time left about once a second
+pub fn wait_for_unlock(clock: &dyn Clock, year: u32, day: u32, mut tick: impl FnMut(Duration)) {
+    while let Some(left) = time_left(clock, year, day) {
+        tick(left);
+        clock.sleep(left.min(Duration::from_secs(1)));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{answer::Answer, day07, day12, day24, params::Params, runner::Day};
+
+// another way to solve a part, compare checks they all agree with the day's own solver
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&str, &Params) -> Answer,
+}
+
+pub const DEFAULT: &str = "default";
+
+fn alternatives(day: u32) -> Vec<Variant> {
+    match day {
+        7 => vec![
+            Variant {
+                name: "descent",
+                part: 1,
+                solve: |text, _| day07::solve1_descent(text),
+            },
+            Variant {
+                name: "brute-force",
+                part: 1,
+                solve: |text, _| day07::solve1_brute_force(text),
+            },
+            Variant {
+                name: "brute-force",
+                part: 2,
+                solve: |text, _| day07::solve2_brute_force(text),
+            },
+        ],
+        12 => vec![
+            Variant {
+                name: "enumerate",
+                part: 1,
+                solve: |text, _| day12::solve1_enumerate(text).into(),
+            },
+            Variant {
+                name: "enumerate",
+                part: 2,
+                solve: |text, _| day12::solve2_enumerate(text).into(),
+            },
+        ],
+        24 => vec![
+            Variant {
+                name: "alu-search",
+                part: 1,
+                solve: |text, _| day24::solve1_search(text).into(),
+            },
+            Variant {
+                name: "alu-search",
+                part: 2,
+                solve: |text, _| day24::solve2_search(text).into(),
+            },
+        ],
+        _ => vec![],
+    }
+}
+
+// the day's own solver first, then the registered alternatives for the part
+pub fn for_part(day: &Day, part: u8) -> Vec<Variant> {
+    let default = Variant {
+        name: DEFAULT,
+        part,
+        solve: day.parts[part as usize - 1],
+    };
+    let mut variants = vec![default];
+    variants.extend(alternatives(day.day).into_iter().filter(|v| v.part == part));
+    variants
+}
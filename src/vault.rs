@@ -0,0 +1,135 @@
+use std::{env, error::Error, fmt::Display, fs, io, path::Path};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+// 64 hex characters, the env var wins over the file
+pub const KEY_VAR: &str = "AOC_VAULT_KEY";
+pub const KEY_PATH: &str = "vault.key";
+pub const VAULT_DIR: &str = "vault";
+
+const MAGIC: &[u8] = b"AOCV1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VaultError {
+    BadKey(String),
+    NotAVaultFile,
+    // wrong key, or the file was changed or moved to another name
+    Tampered,
+}
+
+impl Display for VaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultError::BadKey(e) => write!(f, "bad vault key: {}", e),
+            VaultError::NotAVaultFile => write!(f, "not a vault file"),
+            VaultError::Tampered => write!(
+                f,
+                "can't decrypt, the key is wrong or the file was modified"
+            ),
+        }
+    }
+}
+
+impl Error for VaultError {}
+
+impl From<VaultError> for io::Error {
+    fn from(e: VaultError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+pub struct Vault {
+    cipher: ChaCha20Poly1305,
+}
+
+impl Vault {
+    pub fn new(key: [u8; 32]) -> Self {
+        Vault {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+        }
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, VaultError> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(VaultError::BadKey(String::from(
+                "expected 64 hex characters",
+            )));
+        }
+        let mut key = [0; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                .map_err(|e| VaultError::BadKey(e.to_string()))?;
+        }
+        Ok(Vault::new(key))
+    }
+
+    // None when no key is configured, inputs are kept in plain text then
+    pub fn load() -> io::Result<Option<Self>> {
+        if let Ok(hex) = env::var(KEY_VAR) {
+            return Ok(Some(Vault::from_hex(&hex)?));
+        }
+        match fs::read_to_string(KEY_PATH) {
+            Ok(hex) => Ok(Some(Vault::from_hex(&hex)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io::Error::new(
+                e.kind(),
+                format!("can't read {}: {}", KEY_PATH, e),
+            )),
+        }
+    }
+
+    pub fn generate_key() -> String {
+        ChaCha20Poly1305::generate_key(&mut OsRng)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    // the name is authenticated too, so a file can't be passed off as another day's
+    pub fn encrypt(&self, name: &str, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: plaintext,
+            aad: name.as_bytes(),
+        };
+        let ciphertext = self.cipher.encrypt(&nonce, payload).unwrap();
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, name: &str, data: &[u8]) -> Result<Vec<u8>, VaultError> {
+        let data = data.strip_prefix(MAGIC).ok_or(VaultError::NotAVaultFile)?;
+        if data.len() < NONCE_LEN {
+            return Err(VaultError::NotAVaultFile);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: name.as_bytes(),
+        };
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| VaultError::Tampered)
+    }
+
+    pub fn read(&self, path: &Path) -> io::Result<String> {
+        let plaintext = self.decrypt(&file_name(path), &fs::read(path)?)?;
+        String::from_utf8(plaintext).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn write(&self, path: &Path, text: &str) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.encrypt(&file_name(path), text.as_bytes()))
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned())
+}
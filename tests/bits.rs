@@ -0,0 +1,93 @@
+use adventofcode::bits::{BitError, BitReader, BitWriter};
+
+#[test]
+fn reads_across_bytes() {
+    let mut reader = BitReader::from_hex("D2FE28").unwrap();
+    assert_eq!((reader.len(), reader.remaining()), (24, 24));
+    assert_eq!(reader.read(3), Ok(6));
+    assert_eq!(reader.read(3), Ok(4));
+    assert_eq!(reader.peek(5), Ok(0b10111));
+    assert_eq!(reader.curr(), 6);
+    assert_eq!(reader.read(15), Ok(0b101111111000101));
+    assert_eq!(reader.remaining(), 3);
+    assert_eq!(reader.skip(3), Ok(()));
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn reads_up_to_64_bits() {
+    let mut reader = BitReader::new(vec![0xff; 9]);
+    reader.skip(4).unwrap();
+    assert_eq!(reader.read(64), Ok(u64::MAX));
+    assert_eq!(reader.read(4), Ok(0xf));
+    assert_eq!(reader.read(0), Ok(0));
+}
+
+#[test]
+fn rejects_reads_past_the_end() {
+    let mut reader = BitReader::new(vec![0xab]);
+    reader.read(5).unwrap();
+    let past_the_end = || BitError::OutOfBounds {
+        pos: 5,
+        amount: 4,
+        len: 8,
+    };
+    assert_eq!(reader.peek(4), Err(past_the_end()));
+    assert_eq!(reader.skip(4), Err(past_the_end()));
+    assert_eq!(reader.curr(), 5);
+}
+
+#[test]
+fn rejects_wide_reads_and_writes() {
+    let reader = BitReader::new(vec![0; 16]);
+    assert_eq!(
+        reader.peek(65),
+        Err(BitError::TooWide {
+            amount: 65,
+            max: 64
+        })
+    );
+
+    let mut writer = BitWriter::new();
+    assert_eq!(
+        writer.write(0, 65),
+        Err(BitError::TooWide {
+            amount: 65,
+            max: 64
+        })
+    );
+    assert_eq!(
+        writer.write(0b1_0000_0000, 8),
+        Err(BitError::TooWide { amount: 9, max: 8 })
+    );
+    assert!(writer.is_empty());
+}
+
+#[test]
+fn rejects_bad_hex() {
+    for hex in ["ABC", "ZZ", "0x12"] {
+        assert_eq!(
+            BitReader::from_hex(hex).err(),
+            Some(BitError::BadHex(String::from(hex)))
+        );
+    }
+}
+
+#[test]
+fn writer_round_trips() {
+    let mut writer = BitWriter::new();
+    writer.write(6, 3).unwrap();
+    writer.write(4, 3).unwrap();
+    writer.write(0b101111111000101, 15).unwrap();
+    writer.write(u64::MAX, 64).unwrap();
+    assert_eq!(writer.len(), 85);
+    assert!(writer.to_hex().starts_with("D2FE2"));
+
+    let mut reader = BitReader::from(writer);
+    assert_eq!(reader.len(), 85);
+    assert_eq!(reader.read(3), Ok(6));
+    assert_eq!(reader.read(3), Ok(4));
+    assert_eq!(reader.read(15), Ok(0b101111111000101));
+    assert_eq!(reader.read(64), Ok(u64::MAX));
+    assert_eq!(reader.remaining(), 0);
+}
@@ -0,0 +1,91 @@
+use std::{env, fs, path::Path, process, time::Duration};
+
+use adventofcode::{
+    answer::Answer,
+    dashboard,
+    known::KnownAnswers,
+    report::{Entry, Report},
+    runner::{Failure, PartResult},
+};
+
+fn entry(day: u32, part: u8, answer: &str) -> Entry {
+    Entry::new(
+        day,
+        part,
+        &Ok(PartResult {
+            day,
+            part,
+            answer: Answer::art(answer),
+            elapsed: Duration::from_millis(2),
+            memory: None,
+            cached: false,
+        }),
+    )
+}
+
+fn render(entries: Vec<Entry>, images: &[(u32, Vec<u8>)], page_dir: &Path) -> String {
+    let report = Report {
+        command: String::from("run"),
+        entries,
+    };
+    let known = KnownAnswers::load("tests/fixtures/answers.txt").unwrap();
+    dashboard::render(&report, &known, images, page_dir)
+}
+
+#[test]
+fn counts_stars_against_the_known_answers() {
+    let html = render(
+        vec![entry(1, 1, "7"), entry(1, 2, "6")],
+        &[],
+        Path::new("."),
+    );
+    assert!(html.contains("<span class=\"stars\">1*</span> from the run report"));
+    assert!(html.contains(
+        "<a href=\"https://adventofcode.com/2021/day/1\">Day 1: Sonar Sweep</a> \
+         <span class=\"stars\">*</span><span class=\"missing\">*</span>"
+    ));
+    assert!(html.contains("part 1: <span class=\"answer\">7</span>"));
+    assert!(html.contains("<br>2.000ms<div class=\"bar\" style=\"width: 100.0%\">"));
+}
+
+#[test]
+fn shows_failures() {
+    let failed = Entry::new(2, 1, &Err(Failure::Panic(String::from("oops"))));
+    let html = render(vec![failed], &[], Path::new("."));
+    assert!(html.contains("part 1: <span class=\"failed\">panicked: oops</span>"));
+}
+
+#[test]
+fn escapes_html() {
+    let html = render(
+        vec![entry(3, 1, "<b>\"fish\" & chips</b>")],
+        &[],
+        Path::new("."),
+    );
+    assert!(html.contains("&lt;b&gt;&quot;fish&quot; &amp; chips&lt;/b&gt;"));
+    assert!(!html.contains("<b>"));
+}
+
+#[test]
+fn inlines_images_as_base64() {
+    let images = [
+        (13, b"Man".to_vec()),
+        (20, b"Ma".to_vec()),
+        (25, b"M".to_vec()),
+    ];
+    let html = render(Vec::new(), &images, Path::new("."));
+    for encoded in ["TWFu", "TWE=", "TQ=="] {
+        assert!(html.contains(&format!("src=\"data:image/png;base64,{}\"", encoded)));
+    }
+}
+
+#[test]
+fn links_local_puzzles_next_to_the_page() {
+    let dir = env::temp_dir().join(format!("aoc-dashboard-{}", process::id()));
+    fs::create_dir_all(dir.join("puzzles")).unwrap();
+    fs::write(dir.join("puzzles/day02.md"), "# Dive!").unwrap();
+    let html = render(Vec::new(), &[], &dir);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(html.contains("<a href=\"puzzles/day02.md\">Day 2: Dive!</a>"));
+    assert!(html.contains("<a href=\"https://adventofcode.com/2021/day/3\">"));
+}
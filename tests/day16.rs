@@ -0,0 +1,66 @@
+use adventofcode::day16::{OpType, Packet, PacketError, Payload};
+
+fn packet(hex: &str) -> Packet {
+    hex.parse().unwrap()
+}
+
+#[test]
+fn literal() {
+    let packet = packet("D2FE28");
+    assert_eq!(packet.version, 6);
+    assert!(matches!(packet.payload, Payload::Literal(2021)));
+    assert_eq!(packet.eval(), 2021);
+}
+
+#[test]
+fn version_sums() {
+    for (hex, sum) in [
+        ("8A004A801A8002F478", 16),
+        ("620080001611562C8802118E34", 12),
+        ("C0015000016115A2E0802F182340", 23),
+        ("A0016C880162017C3686B18A3D4780", 31),
+    ] {
+        assert_eq!(packet(hex).version_sum(), sum, "{}", hex);
+    }
+}
+
+#[test]
+fn expressions() {
+    for (hex, value) in [
+        ("C200B40A82", 3),
+        ("04005AC33890", 54),
+        ("880086C3E88112", 7),
+        ("CE00C43D881120", 9),
+        ("D8005AC2A8F0", 1),
+        ("F600BC2D8F", 0),
+        ("9C005AC2F8F0", 0),
+        ("9C0141080250320F1802104A08", 1),
+    ] {
+        assert_eq!(packet(hex).eval(), value, "{}", hex);
+    }
+}
+
+#[test]
+fn bad_hex_is_an_error() {
+    assert!("D2FG28".parse::<Packet>().is_err());
+}
+
+#[test]
+fn operators_with_the_wrong_subpacket_count_are_errors() {
+    // a greater-than over a single literal
+    assert_eq!(
+        "16004408".parse::<Packet>(),
+        Err(PacketError::Operands {
+            op: OpType::Gt,
+            count: 1
+        })
+    );
+    // a minimum over no subpackets
+    assert_eq!(
+        "0A0000".parse::<Packet>(),
+        Err(PacketError::Operands {
+            op: OpType::Min,
+            count: 0
+        })
+    );
+}
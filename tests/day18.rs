@@ -0,0 +1,67 @@
+use adventofcode::day18::{self, Number};
+
+const EXAMPLE: &str = include_str!("fixtures/day18.txt");
+
+fn number(s: &str) -> Number {
+    s.parse().unwrap()
+}
+
+fn sum(numbers: &[&str]) -> Number {
+    numbers
+        .iter()
+        .map(|s| number(s))
+        .reduce(|a, b| a + b)
+        .unwrap()
+}
+
+#[test]
+fn adds_and_reduces() {
+    assert_eq!(
+        number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]"),
+        number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+    );
+    assert_eq!(
+        sum(&["[1,1]", "[2,2]", "[3,3]", "[4,4]"]).to_string(),
+        "[[[[1,1],[2,2]],[3,3]],[4,4]]"
+    );
+    assert_eq!(
+        sum(&["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]).to_string(),
+        "[[[[5,0],[7,4]],[5,5]],[6,6]]"
+    );
+}
+
+#[test]
+fn adds_plain_numbers() {
+    assert_eq!(Number::Simple(1) + Number::Simple(2), number("[1,2]"));
+    assert_eq!(number("[1,2]") + Number::Simple(9), number("[[1,2],9]"));
+}
+
+#[test]
+fn magnitudes() {
+    for (s, magnitude) in [
+        ("[[1,2],[[3,4],5]]", 143),
+        ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+        ("[[[[1,1],[2,2]],[3,3]],[4,4]]", 445),
+        ("[[[[3,0],[5,3]],[4,4]],[5,5]]", 791),
+        ("[[[[5,0],[7,4]],[5,5]],[6,6]]", 1137),
+        (
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+            3488,
+        ),
+    ] {
+        assert_eq!(number(s).magnitude(), magnitude, "{}", s);
+    }
+}
+
+#[test]
+fn rejects_malformed_numbers() {
+    for s in ["[1,2]xyz", "[1,2", "[1;2]", "", "[a,2]"] {
+        assert!(s.parse::<Number>().is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn example() {
+    assert_eq!(day18::solve1(EXAMPLE), 4140);
+    assert_eq!(day18::solve2(EXAMPLE), 3993);
+}
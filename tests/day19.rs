@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use adventofcode::day19::{self, Point, Scanner};
+
+// scanner 0's beacons, irregular enough that only one turn lines them up
+const BEACONS: &[(i32, i32, i32)] = &[
+    (404, -588, -901),
+    (528, -643, 409),
+    (-838, 591, 734),
+    (390, -675, -793),
+    (-537, -823, -458),
+    (-485, -357, 347),
+    (-345, -311, 381),
+    (-661, -816, -575),
+    (-876, 649, 763),
+    (-618, -824, -621),
+    (553, 345, -567),
+    (474, 580, 667),
+];
+
+const POSITION: Point = Point(68, -1246, -43);
+
+fn scanner(id: usize, points: impl IntoIterator<Item = Point>) -> Scanner {
+    Scanner {
+        id,
+        points: points.into_iter().collect(),
+        pos: Point(0, 0, 0),
+    }
+}
+
+fn beacons() -> Vec<Point> {
+    BEACONS.iter().map(|&(x, y, z)| Point(x, y, z)).collect()
+}
+
+// what a scanner at POSITION sees when it is turned around z and x
+fn seen_from_position() -> Scanner {
+    scanner(
+        1,
+        beacons()
+            .into_iter()
+            .map(|p| (p - POSITION).rotate_z(3).rotate_x(2)),
+    )
+}
+
+#[test]
+fn rotations_come_back_around() {
+    let p = Point(1, 2, 3);
+    assert_eq!(p.rotate_x(4), p);
+    assert_eq!(p.rotate_y(1).rotate_y(3), p);
+    assert_eq!(p.rotate_z(1), Point(2, -1, 3));
+    assert_eq!(p.sum_abs(), 6);
+}
+
+#[test]
+fn aligns_an_overlapping_scanner() {
+    let base = scanner(0, beacons());
+    let (points, pos) = seen_from_position().try_adjust_for(&base, 12).unwrap();
+    assert_eq!(pos, POSITION);
+    assert_eq!(points, base.points);
+}
+
+#[test]
+fn leaves_scanners_without_overlap() {
+    let base = scanner(0, beacons().into_iter().take(3));
+    assert!(seen_from_position().try_adjust_for(&base, 12).is_none());
+}
+
+#[test]
+fn adjusts_every_reachable_scanner() {
+    let mut scanners = vec![
+        scanner(0, beacons()),
+        seen_from_position(),
+        scanner(2, [Point(1, 1, 1), Point(5, 9, 2)]),
+    ];
+    day19::adjust_scanners(&mut scanners, 12);
+    assert_eq!(scanners[1].pos, POSITION);
+    assert_eq!(scanners[2].pos, Point(0, 0, 0));
+
+    let all: HashSet<Point> = scanners[..2]
+        .iter()
+        .flat_map(|s| s.points.iter().cloned())
+        .collect();
+    assert_eq!(all.len(), BEACONS.len());
+}
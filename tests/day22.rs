@@ -0,0 +1,27 @@
+use adventofcode::day22::{self, Action, Step};
+
+const SMALL: &str = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+";
+
+#[test]
+fn small_example() {
+    let steps = day22::get_inputs(SMALL);
+    assert_eq!(steps[2].action, Action::Off);
+    assert_eq!(steps[0].cuboid.volume(), 27);
+    assert_eq!(day22::volume_after_steps(steps), 39);
+    assert_eq!(day22::solve1(SMALL), 39);
+}
+
+#[test]
+fn rejects_spans_past_the_integer_limit() {
+    let line = format!("on x=0..{},y=0..1,z=0..1", i32::MAX);
+    assert!(line.parse::<Step>().is_err());
+    let line = format!("on x=0..{},y=0..1,z=0..1", i32::MAX - 1);
+    assert_eq!(
+        line.parse::<Step>().unwrap().cuboid.volume(),
+        (i32::MAX as u64) * 4
+    );
+}
@@ -0,0 +1,19 @@
+use adventofcode::grid::Grid;
+
+#[test]
+fn blank_lines_around_the_grid_are_skipped() {
+    let grid: Grid<char> = "\n\nab\ncd\n\n".parse().unwrap();
+    assert_eq!((grid.height(), grid.width()), (2, 2));
+    assert_eq!(grid[(1, 0)], 'c');
+}
+
+#[test]
+fn blank_line_inside_the_grid_is_an_error() {
+    let err = "ab\n\ncd\n".parse::<Grid<char>>().unwrap_err();
+    assert_eq!(err, "line 2 is blank in the middle of the grid");
+}
+
+#[test]
+fn rows_must_have_the_same_width() {
+    assert!("ab\nc\n".parse::<Grid<char>>().is_err());
+}
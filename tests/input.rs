@@ -0,0 +1,74 @@
+use adventofcode::input::{self, InputError};
+
+#[test]
+fn strips_the_bom() {
+    assert_eq!(
+        input::sanitize("\u{feff}199\n200\n"),
+        Ok(String::from("199\n200\n"))
+    );
+}
+
+#[test]
+fn turns_crlf_into_lf() {
+    assert_eq!(
+        input::sanitize("..#\r\n#..\r\n\r\n.#.\r\n"),
+        Ok(String::from("..#\n#..\n\n.#.\n"))
+    );
+}
+
+#[test]
+fn trims_trailing_whitespace() {
+    assert_eq!(
+        input::sanitize("forward 5  \nup 3\t\n\n\n  \n"),
+        Ok(String::from("forward 5\nup 3\n"))
+    );
+    assert_eq!(input::sanitize("3,4,3"), Ok(String::from("3,4,3\n")));
+    // leading indentation can be part of the puzzle
+    assert_eq!(input::sanitize("  a\n b\n"), Ok(String::from("  a\n b\n")));
+}
+
+#[test]
+fn rejects_empty_input() {
+    assert_eq!(input::sanitize(""), Err(InputError::Empty));
+    assert_eq!(input::sanitize(" \r\n\n"), Err(InputError::Empty));
+}
+
+#[test]
+fn rejects_the_logged_out_page() {
+    let page = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+    assert_eq!(input::sanitize(page), Err(InputError::LoggedOut));
+}
+
+#[test]
+fn rejects_the_locked_page() {
+    let page = "Please don't repeatedly request this endpoint before it unlocks! \
+                The calendar countdown is synchronized with the server time; \
+                the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+    assert_eq!(input::sanitize(page), Err(InputError::NotUnlocked));
+}
+
+#[test]
+fn rejects_html() {
+    for page in [
+        "<!DOCTYPE html>\n<html lang=\"en-us\">\n<head></head>\n</html>\n",
+        "<p>maintenance</p>\n",
+        "\n\n<html>\n<body>oops</body>\n</html>\n",
+    ] {
+        assert_eq!(input::sanitize(page), Err(InputError::Html), "{:?}", page);
+    }
+}
+
+#[test]
+fn rejects_server_errors() {
+    for line in [
+        "404 Not Found",
+        "500 Internal Server Error",
+        "502 Bad Gateway",
+        "503 Service Unavailable",
+    ] {
+        assert_eq!(
+            input::sanitize(&format!("{}\n", line)),
+            Err(InputError::ServerError(String::from(line)))
+        );
+    }
+}
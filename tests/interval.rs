@@ -0,0 +1,75 @@
+use adventofcode::interval::{Interval, IntervalBox, IntervalSet};
+
+fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+    intervals
+        .iter()
+        .map(|&(start, end)| Interval::new(start, end))
+        .collect()
+}
+
+fn intervals(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+    set.iter().map(|i| (i.start, i.end)).collect()
+}
+
+#[test]
+fn merges_overlapping_and_adjacent_intervals() {
+    let set = set(&[(5, 8), (0, 3), (3, 4), (7, 10), (12, 12), (20, 25)]);
+    assert_eq!(intervals(&set), vec![(0, 4), (5, 10), (20, 25)]);
+    assert_eq!(set.len(), 14);
+}
+
+#[test]
+fn inserts_into_a_normalized_set() {
+    let mut set = IntervalSet::new();
+    assert!(set.is_empty());
+    set.insert(Interval::new(10, 20));
+    set.insert(Interval::new(0, 5));
+    set.insert(Interval::new(5, 10));
+    assert_eq!(intervals(&set), vec![(0, 20)]);
+    assert_eq!(set.len(), 20);
+}
+
+#[test]
+fn membership() {
+    let set = set(&[(0, 3), (5, 8)]);
+    for (x, inside) in [
+        (-1, false),
+        (0, true),
+        (2, true),
+        (3, false),
+        (5, true),
+        (8, false),
+    ] {
+        assert_eq!(set.contains(&x), inside, "{}", x);
+    }
+}
+
+#[test]
+fn union_and_intersection() {
+    let a = set(&[(0, 5), (10, 15)]);
+    let b = set(&[(3, 12), (20, 22)]);
+    assert_eq!(intervals(&a.union(&b)), vec![(0, 15), (20, 22)]);
+    assert_eq!(intervals(&a.intersection(&b)), vec![(3, 5), (10, 12)]);
+    assert!(a.intersection(&set(&[(5, 10)])).is_empty());
+}
+
+#[test]
+fn subtraction_splits_intervals() {
+    let a = set(&[(0, 10), (20, 30)]);
+    let b = set(&[(3, 5), (8, 22), (28, 40)]);
+    assert_eq!(intervals(&a.difference(&b)), vec![(0, 3), (5, 8), (22, 28)]);
+    assert_eq!(a.difference(&b).len(), 3 + 3 + 6);
+    assert!(a.difference(&a).is_empty());
+    assert_eq!(a.difference(&IntervalSet::new()), a);
+}
+
+#[test]
+fn box_volume_and_intersection() {
+    let a = IntervalBox([Interval::new(10, 13); 3]);
+    let b = IntervalBox([Interval::new(11, 14); 3]);
+    assert_eq!(a.volume(), 27);
+    assert_eq!(a.intersection(&b).unwrap().volume(), 8);
+    assert!(a.contains(&[12, 10, 11]));
+    assert!(!a.contains(&[13, 10, 11]));
+    assert_eq!(a.clamp(&Interval::new(0, 12)).volume(), 8);
+}
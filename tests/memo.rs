@@ -0,0 +1,69 @@
+use adventofcode::memo::{CacheStats, Memo};
+
+fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+    memo.get_or_compute(n, |memo| {
+        if n < 2 {
+            n
+        } else {
+            fib(n - 1, memo) + fib(n - 2, memo)
+        }
+    })
+}
+
+#[test]
+fn starts_empty() {
+    let memo: Memo<u64, u64> = Memo::new();
+    assert_eq!(memo.stats(), CacheStats::default());
+    assert_eq!(memo.stats().hit_rate(), 0.0);
+}
+
+#[test]
+fn counts_hits_and_misses() {
+    let mut memo = Memo::new();
+    assert_eq!(memo.get_or_compute("a", |_| 1), 1);
+    assert_eq!(memo.get_or_compute("b", |_| 2), 2);
+    assert_eq!(memo.get_or_compute("a", |_| unreachable!()), 1);
+    assert_eq!(
+        memo.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 2,
+            entries: 2
+        }
+    );
+    assert_eq!(
+        memo.stats().to_string(),
+        "1 hits, 2 misses, 2 entries (33.3% hit rate)"
+    );
+}
+
+#[test]
+fn recurses_through_the_memo() {
+    let mut memo = Memo::new();
+    assert_eq!(fib(50, &mut memo), 12586269025);
+    // every n from 0 to 50 is computed once, every n from 1 to 48 is found the
+    // second time it's asked for
+    assert_eq!(
+        memo.stats(),
+        CacheStats {
+            hits: 48,
+            misses: 51,
+            entries: 51
+        }
+    );
+}
+
+#[test]
+fn reuses_the_cache_across_calls() {
+    let mut memo = Memo::new();
+    fib(30, &mut memo);
+    let before = memo.stats();
+    assert_eq!(fib(30, &mut memo), 832040);
+    assert_eq!(fib(20, &mut memo), 6765);
+    let after = memo.stats();
+    assert_eq!(after.hits, before.hits + 2);
+    assert_eq!(
+        (after.misses, after.entries),
+        (before.misses, before.entries)
+    );
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use adventofcode::{
+    client::{self, Client, ClientError, Config, SubmitOutcome},
+    input::{self, InputError},
+    mock::MockServer,
+};
+
+const SESSION: &str = "test-session";
+const INPUT_URL: &str = "https://adventofcode.com/2021/day/1/input";
+const ANSWER_URL: &str = "https://adventofcode.com/2021/day/1/answer";
+
+fn start() -> MockServer {
+    MockServer::start(0, "tests/fixtures", SESSION).unwrap()
+}
+
+fn client(server: &MockServer, session: &str) -> Client {
+    Client::new(Config {
+        base_url: server.base_url(),
+        session: String::from(session),
+        min_interval: Duration::ZERO,
+        backoff: Duration::from_millis(10),
+        ..Config::default()
+    })
+}
+
+fn submit(client: &Client, level: &str, answer: &str) -> SubmitOutcome {
+    let page = client
+        .post_form(ANSWER_URL, &[("level", level), ("answer", answer)])
+        .unwrap();
+    SubmitOutcome::parse(&page)
+}
+
+#[test]
+fn fetches_the_input() {
+    let server = start();
+    let text = client(&server, SESSION).get(INPUT_URL).unwrap();
+    let text = input::sanitize(&text).unwrap();
+    assert_eq!(adventofcode::day01::solve1(&text), 7);
+    assert_eq!(server.requests(), vec!["GET /2021/day/1/input"]);
+}
+
+#[test]
+fn rejects_a_wrong_session() {
+    let server = start();
+    let text = client(&server, "expired").get(INPUT_URL).unwrap();
+    assert_eq!(input::sanitize(&text), Err(InputError::LoggedOut));
+}
+
+#[test]
+fn parses_the_puzzle_title() {
+    let server = start();
+    let page = client(&server, SESSION)
+        .get(&client::puzzle_url(2021, 1))
+        .unwrap();
+    assert_eq!(client::puzzle_title(&page).as_deref(), Some("Sonar Sweep"));
+}
+
+#[test]
+fn answers_and_rate_limits_submissions() {
+    let server = start();
+    let client = client(&server, SESSION);
+    assert_eq!(
+        submit(&client, "1", "9"),
+        SubmitOutcome::Wrong(Some(String::from("too high")))
+    );
+    assert!(matches!(
+        submit(&client, "1", "7"),
+        SubmitOutcome::TooSoon(_)
+    ));
+
+    server.set_cooldown(Duration::ZERO);
+    assert_eq!(submit(&client, "1", "7"), SubmitOutcome::Correct);
+    assert_eq!(submit(&client, "1", "7"), SubmitOutcome::AlreadySolved);
+    assert_eq!(
+        submit(&client, "2", "3"),
+        SubmitOutcome::Wrong(Some(String::from("too low")))
+    );
+}
+
+#[test]
+fn retries_server_errors() {
+    let server = start();
+    let client = client(&server, SESSION);
+
+    server.fail_next(2);
+    assert!(client.get(INPUT_URL).is_ok());
+    assert_eq!(server.requests().len(), 3);
+
+    server.fail_next(10);
+    assert!(matches!(
+        client.get(INPUT_URL),
+        Err(ClientError::Status(503))
+    ));
+    assert_eq!(
+        server.requests().len(),
+        3 + 1 + client.config().retries as usize
+    );
+}
+
+#[test]
+fn submits_an_answer_once() {
+    let server = start();
+    let client = client(&server, SESSION);
+
+    server.fail_next(1);
+    assert!(matches!(
+        client.post_form(ANSWER_URL, &[("level", "1"), ("answer", "7")]),
+        Err(ClientError::Status(503))
+    ));
+    assert_eq!(server.requests(), vec!["POST /2021/day/1/answer"]);
+}
+
+#[test]
+fn keeps_the_minimum_interval() {
+    let server = start();
+    let client = Client::new(Config {
+        min_interval: Duration::from_millis(200),
+        ..client(&server, SESSION).config().clone()
+    });
+    let now = std::time::Instant::now();
+    for _ in 0..3 {
+        client.get(INPUT_URL).unwrap();
+    }
+    assert!(now.elapsed() >= Duration::from_millis(400));
+}
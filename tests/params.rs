@@ -0,0 +1,52 @@
+use adventofcode::{
+    params::{Param, Params},
+    runner,
+};
+
+const DECLARED: &[Param] = &[Param {
+    name: "steps",
+    parts: &[1],
+    default: 10,
+    min: 0,
+    max: 40,
+}];
+
+#[test]
+fn falls_back_to_the_default() {
+    assert_eq!(Params::new().get(DECLARED, "steps"), 10);
+    let params: Params = "steps=40".parse().unwrap();
+    assert_eq!(params.get(DECLARED, "steps"), 40);
+    assert!(params.overrides(DECLARED, 1).iter().eq([("steps", 40)]));
+    assert!(params.overrides(DECLARED, 2).is_empty());
+}
+
+#[test]
+fn rejects_values_out_of_range() {
+    for given in ["steps=-1", "steps=41"] {
+        let params: Params = given.parse().unwrap();
+        assert!(params.check(DECLARED).is_err(), "{}", given);
+    }
+    let params: Params = "steps=0,other=-5".parse().unwrap();
+    assert_eq!(params.check(DECLARED), Ok(()));
+}
+
+#[test]
+fn rejects_malformed_pairs() {
+    assert!("steps".parse::<Params>().is_err());
+    assert!("steps=many".parse::<Params>().is_err());
+}
+
+#[test]
+fn defaults_are_in_range() {
+    for day in runner::DAYS.iter() {
+        for param in day.params.iter() {
+            assert!(
+                (param.min..=param.max).contains(&param.default)
+                    && param.parts.iter().all(|part| (1..=2).contains(part)),
+                "day{:02} {}",
+                day.day,
+                param.name
+            );
+        }
+    }
+}
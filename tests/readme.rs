@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use adventofcode::{
+    answer::Answer,
+    known::KnownAnswers,
+    readme::{self, END_MARKER, START_MARKER},
+    report::{Entry, Report},
+    runner::{self, PartResult},
+};
+
+fn known() -> KnownAnswers {
+    KnownAnswers::load("tests/fixtures/answers.txt").unwrap()
+}
+
+fn entry(day: u32, part: u8, millis: u64) -> Entry {
+    Entry::new(
+        day,
+        part,
+        &Ok(PartResult {
+            day,
+            part,
+            answer: Answer::from(0u32),
+            elapsed: Duration::from_millis(millis),
+            memory: None,
+            cached: false,
+        }),
+    )
+}
+
+fn row(table: &str, day: u32) -> &str {
+    table
+        .lines()
+        .find(|l| l.starts_with(&format!("| {} |", day)))
+        .unwrap()
+}
+
+#[test]
+fn renders_a_row_per_day() {
+    let report = Report {
+        command: String::from("bench"),
+        entries: vec![entry(1, 1, 2), entry(1, 2, 3), entry(23, 1, 1500)],
+    };
+    let table = readme::progress_table(&known(), Some(&report));
+    let mut lines = table.lines();
+    assert_eq!(
+        lines.next(),
+        Some("| Day | Title | Solved | Runtime | Source |")
+    );
+    assert_eq!(lines.next(), Some("| ---: | --- | :---: | ---: | --- |"));
+    assert_eq!(lines.count(), runner::DAYS.len());
+
+    assert_eq!(
+        row(&table, 1),
+        "| 1 | [Sonar Sweep](https://adventofcode.com/2021/day/1) | ★★ | 5.00ms | \
+         [src/day01.rs](src/day01.rs) |"
+    );
+    assert!(row(&table, 2).contains("| ☆☆ | - |"));
+    assert!(row(&table, 23).contains("| ☆☆ | 1.50s |"));
+}
+
+#[test]
+fn runtimes_need_a_report() {
+    let table = readme::progress_table(&known(), None);
+    assert!(row(&table, 1).contains("| ★★ | - |"));
+}
+
+#[test]
+fn replaces_only_the_marked_section() {
+    let readme = format!(
+        "# Advent of Code\n\n{}\nold table\n{}\n\nmore text\n",
+        START_MARKER, END_MARKER
+    );
+    let updated = readme::update(&readme, "new table\n").unwrap();
+    assert_eq!(
+        updated,
+        format!(
+            "# Advent of Code\n\n{}\nnew table\n{}\n\nmore text\n",
+            START_MARKER, END_MARKER
+        )
+    );
+    assert_eq!(readme::update(&updated, "new table\n").unwrap(), updated);
+}
+
+#[test]
+fn appends_the_section_when_the_markers_are_missing() {
+    let updated = readme::update("# Advent of Code\n\n", "table\n").unwrap();
+    assert_eq!(
+        updated,
+        format!(
+            "# Advent of Code\n\n{}\ntable\n{}\n",
+            START_MARKER, END_MARKER
+        )
+    );
+}
+
+#[test]
+fn rejects_markers_out_of_order() {
+    let readme = format!("{}\n{}\n", END_MARKER, START_MARKER);
+    assert!(readme::update(&readme, "table\n").is_err());
+    assert!(readme::update(START_MARKER, "table\n").is_err());
+}
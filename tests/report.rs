@@ -0,0 +1,126 @@
+use std::{env, fs, process, time::Duration};
+
+use adventofcode::{
+    answer::Answer,
+    report::{Entry, Report, Status},
+    runner::{Failure, PartResult},
+};
+
+fn solved(answer: Answer, millis: u64) -> Result<PartResult, Failure> {
+    Ok(PartResult {
+        day: 1,
+        part: 2,
+        answer,
+        elapsed: Duration::from_millis(millis),
+        memory: Some(1024),
+        cached: false,
+    })
+}
+
+#[test]
+fn entries_for_each_status() {
+    let ok = Entry::new(
+        1,
+        2,
+        &solved(Answer::from(5u32).with_details("window of 3"), 4),
+    );
+    assert_eq!(ok.status, Status::Ok);
+    assert_eq!(ok.answer.as_deref(), Some("5"));
+    assert_eq!(ok.details.as_deref(), Some("window of 3"));
+    assert_eq!(
+        (ok.time_ms, ok.runs, ok.memory_bytes),
+        (Some(4.0), 1, Some(1024))
+    );
+    assert_eq!(ok.error, None);
+
+    let art = Entry::new(13, 2, &solved(Answer::art("#.\n.#"), 1));
+    assert_eq!(art.answer.as_deref(), Some("#.\n.#"));
+
+    for (failure, status, error) in [
+        (
+            Failure::Error(String::from("no input")),
+            Status::Error,
+            "error: no input",
+        ),
+        (
+            Failure::Timeout(Duration::from_secs(2)),
+            Status::Timeout,
+            "timed out after 2s",
+        ),
+        (
+            Failure::Panic(String::from("oops")),
+            Status::Panic,
+            "panicked: oops",
+        ),
+    ] {
+        let entry = Entry::new(1, 1, &Err(failure));
+        assert_eq!(entry.status, status);
+        assert_eq!(entry.error.as_deref(), Some(error));
+        assert_eq!((entry.answer, entry.time_ms, entry.runs), (None, None, 0));
+    }
+}
+
+#[test]
+fn folds_runs_together() {
+    let mut entry = Entry::new(1, 2, &solved(Answer::from(5u32), 4));
+    entry.add_run(Entry::new(1, 2, &solved(Answer::from(5u32), 2)));
+    entry.add_run(Entry::new(1, 2, &solved(Answer::from(5u32), 9)));
+    assert_eq!(entry.runs, 3);
+    assert_eq!(entry.time_ms, Some(5.0));
+    assert_eq!(entry.min_time_ms, Some(2.0));
+
+    entry.add_run(Entry::new(1, 2, &Err(Failure::Panic(String::from("oops")))));
+    assert_eq!(entry.status, Status::Panic);
+    entry.add_run(Entry::new(1, 2, &solved(Answer::from(5u32), 1)));
+    assert_eq!(entry.status, Status::Panic);
+}
+
+#[test]
+fn renders_json() {
+    let mut report = Report::new("run");
+    report
+        .entries
+        .push(Entry::new(1, 1, &solved(Answer::from(7u32), 1)));
+    report.entries.push(Entry::new(
+        1,
+        2,
+        &Err(Failure::Timeout(Duration::from_secs(1))),
+    ));
+    let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+    assert_eq!(json["command"], "run");
+    assert_eq!(
+        json["entries"][0],
+        serde_json::json!({
+            "day": 1,
+            "part": 1,
+            "status": "ok",
+            "answer": "7",
+            "details": null,
+            "time_ms": 1.0,
+            "min_time_ms": 1.0,
+            "runs": 1,
+            "memory_bytes": 1024,
+            "error": null,
+            "cached": false
+        })
+    );
+    assert_eq!(json["entries"][1]["status"], "timeout");
+    assert_eq!(json["entries"][1]["error"], "timed out after 1s");
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let mut report = Report::new("bench");
+    let mut entry = Entry::new(3, 1, &solved(Answer::from(198u32), 3));
+    entry.profile = Some(String::from("alice"));
+    report.entries.push(entry);
+
+    let path = env::temp_dir().join(format!("aoc-report-{}.json", process::id()));
+    report.write(&path).unwrap();
+    let loaded = Report::load(&path).unwrap();
+    fs::remove_file(path).unwrap();
+    assert_eq!(loaded.command, "bench");
+    assert_eq!(loaded.entries[0].profile.as_deref(), Some("alice"));
+    assert_eq!(loaded.entries[0].answer.as_deref(), Some("198"));
+    assert_eq!(loaded.to_json(), report.to_json());
+}
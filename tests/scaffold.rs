@@ -0,0 +1,63 @@
+use std::{env, fs, path::PathBuf, process};
+
+use adventofcode::scaffold;
+
+const LIB: &str = "pub mod day01;\npub mod day03;\npub mod grid;\n";
+const RUNNER: &str = "days!(\n    1 => day01,\n    3 => day03,\n);\n";
+
+fn repo(name: &str) -> PathBuf {
+    let root = env::temp_dir().join(format!("aoc-scaffold-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), LIB).unwrap();
+    fs::write(root.join("src/runner.rs"), RUNNER).unwrap();
+    root
+}
+
+#[test]
+fn writes_and_registers_the_day() {
+    let root = repo("writes");
+    let paths = scaffold::scaffold(&root, 2022, 2, "Rock \"Paper\" Scissors").unwrap();
+    assert_eq!(paths.len(), 4);
+
+    let source = fs::read_to_string(root.join("src/day02.rs")).unwrap();
+    assert!(source.contains("\"https://adventofcode.com/2022/day/2/input\""));
+    assert!(source.contains("pub const TITLE: &str = \"Rock \\\"Paper\\\" Scissors\";"));
+    assert!(source.contains("include_str!(\"../fixtures/day02.txt\")"));
+    assert_eq!(
+        fs::read_to_string(root.join("fixtures/day02.txt")).unwrap(),
+        ""
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "pub mod day01;\npub mod day02;\npub mod day03;\npub mod grid;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("src/runner.rs")).unwrap(),
+        "days!(\n    1 => day01,\n    3 => day03,\n    2 => day02,\n);\n"
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn existing_day_is_left_alone() {
+    let root = repo("existing");
+    fs::write(root.join("src/day01.rs"), "// solved").unwrap();
+    assert!(scaffold::scaffold(&root, 2021, 1, "Sonar Sweep").is_err());
+    assert_eq!(
+        fs::read_to_string(root.join("src/day01.rs")).unwrap(),
+        "// solved"
+    );
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), LIB);
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn days_outside_the_calendar_are_rejected() {
+    let root = repo("range");
+    assert!(scaffold::scaffold(&root, 2021, 0, "Nothing").is_err());
+    assert!(scaffold::scaffold(&root, 2021, 26, "Nothing").is_err());
+    assert!(scaffold::scaffold(&root, 2014, 1, "Nothing").is_err());
+    assert!(!root.join("fixtures").exists());
+    fs::remove_dir_all(root).unwrap();
+}
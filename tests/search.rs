@@ -0,0 +1,68 @@
+use adventofcode::{day15, day23, search};
+
+// 0 -> 1 -> 3 costs 1 + 5, 0 -> 2 -> 3 costs 2 + 1, 4 can't be reached
+fn edges(n: &u32) -> Vec<(u32, u32)> {
+    match n {
+        0 => vec![(1, 1), (2, 2)],
+        1 => vec![(3, 5)],
+        2 => vec![(3, 1)],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn dijkstra_takes_the_cheapest_path() {
+    assert_eq!(
+        search::dijkstra(&0, edges, |n| *n == 3),
+        Some((vec![0, 2, 3], 3))
+    );
+}
+
+#[test]
+fn astar_with_an_admissible_heuristic_agrees_with_dijkstra() {
+    let heuristic = |n: &u32| if *n == 3 { 0 } else { 1 };
+    assert_eq!(
+        search::astar(&0, edges, heuristic, |n| *n == 3),
+        Some((vec![0, 2, 3], 3))
+    );
+}
+
+#[test]
+fn bfs_takes_the_fewest_steps() {
+    let successors = |n: &u32| edges(n).into_iter().map(|(n, _)| n);
+    assert_eq!(
+        search::bfs(&0, successors, |n| *n == 3),
+        Some((vec![0, 1, 3], 2))
+    );
+}
+
+#[test]
+fn start_can_be_the_goal() {
+    assert_eq!(search::dijkstra(&3, edges, |n| *n == 3), Some((vec![3], 0)));
+    assert_eq!(
+        search::bfs(&3, |_: &u32| Vec::new(), |n| *n == 3),
+        Some((vec![3], 0))
+    );
+}
+
+#[test]
+fn unreachable_goal() {
+    assert_eq!(search::dijkstra(&0, edges, |n| *n == 4), None);
+    assert_eq!(search::astar(&0, edges, |_| 0, |n| *n == 4), None);
+    let successors = |n: &u32| edges(n).into_iter().map(|(n, _)| n);
+    assert_eq!(search::bfs(&0, successors, |n| *n == 4), None);
+}
+
+#[test]
+fn day15_example() {
+    let text = include_str!("fixtures/day15.txt");
+    assert_eq!(day15::solve1(text), 40);
+    assert_eq!(day15::solve2(text), 315);
+}
+
+#[test]
+fn day23_example() {
+    let text = include_str!("fixtures/day23.txt");
+    assert_eq!(day23::solve1(text), 12521);
+    assert_eq!(day23::solve2(text), 44169);
+}
@@ -0,0 +1,58 @@
+use std::{
+    cell::Cell,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use adventofcode::unlock::{self, Clock};
+
+struct FakeClock(Cell<SystemTime>);
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        self.0.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.0.set(self.0.get() + duration);
+    }
+}
+
+fn at(unix: u64) -> FakeClock {
+    FakeClock(Cell::new(UNIX_EPOCH + Duration::from_secs(unix)))
+}
+
+#[test]
+fn unlocks_at_midnight_eastern() {
+    // 2021-12-01T05:00:00Z and 2021-12-25T05:00:00Z
+    assert_eq!(
+        unlock::unlock_time(2021, 1),
+        UNIX_EPOCH + Duration::from_secs(1638334800)
+    );
+    assert_eq!(
+        unlock::unlock_time(2021, 25),
+        UNIX_EPOCH + Duration::from_secs(1640408400)
+    );
+}
+
+#[test]
+fn counts_down_until_unlocked() {
+    let clock = at(1638334800 - 3725);
+    let left = unlock::time_left(&clock, 2021, 1).unwrap();
+    assert_eq!(unlock::format_countdown(left), "01:02:05");
+    assert_eq!(
+        unlock::format_countdown(left + Duration::from_secs(86400)),
+        "1d 01:02:05"
+    );
+
+    let clock = at(1638334800);
+    assert_eq!(unlock::time_left(&clock, 2021, 1), None);
+}
+
+#[test]
+fn waits_for_the_unlock() {
+    let clock = at(1638334800 - 3);
+    let mut ticks = Vec::new();
+    unlock::wait_for_unlock(&clock, 2021, 1, |left| ticks.push(left.as_secs()));
+    assert_eq!(ticks, vec![3, 2, 1]);
+    assert_eq!(unlock::time_left(&clock, 2021, 1), None);
+}
@@ -0,0 +1,54 @@
+use std::{env, fs, process};
+
+use adventofcode::vault::{Vault, VaultError};
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+const OTHER_KEY: &str = "ff0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+#[test]
+fn round_trips() {
+    let vault = Vault::from_hex(KEY).unwrap();
+    let data = vault.encrypt("day01.enc", b"199\n200\n");
+    assert_eq!(vault.decrypt("day01.enc", &data).unwrap(), b"199\n200\n");
+
+    let path = env::temp_dir().join(format!("aoc-vault-{}", process::id()));
+    let file = path.join("day01.enc");
+    vault.write(&file, "199\n200\n").unwrap();
+    assert_ne!(fs::read(&file).unwrap(), b"199\n200\n");
+    assert_eq!(vault.read(&file).unwrap(), "199\n200\n");
+    fs::remove_dir_all(path).unwrap();
+}
+
+#[test]
+fn rejects_the_wrong_key() {
+    let data = Vault::from_hex(KEY).unwrap().encrypt("day01.enc", b"199\n");
+    let other = Vault::from_hex(OTHER_KEY).unwrap();
+    assert_eq!(other.decrypt("day01.enc", &data), Err(VaultError::Tampered));
+}
+
+#[test]
+fn rejects_tampered_files() {
+    let vault = Vault::from_hex(KEY).unwrap();
+    let data = vault.encrypt("day01.enc", b"199\n");
+
+    let mut flipped = data.clone();
+    *flipped.last_mut().unwrap() ^= 1;
+    assert_eq!(
+        vault.decrypt("day01.enc", &flipped),
+        Err(VaultError::Tampered)
+    );
+    assert_eq!(vault.decrypt("day02.enc", &data), Err(VaultError::Tampered));
+    assert_eq!(
+        vault.decrypt("day01.enc", b"199\n"),
+        Err(VaultError::NotAVaultFile)
+    );
+}
+
+#[test]
+fn rejects_bad_keys() {
+    assert!(matches!(Vault::from_hex("abc"), Err(VaultError::BadKey(_))));
+    assert!(matches!(
+        Vault::from_hex(&KEY.replace('0', "g")),
+        Err(VaultError::BadKey(_))
+    ));
+}